@@ -2,19 +2,33 @@
 
 mod am_conn_core;
 mod authentication;
+mod cancel_handle;
 mod connection_core;
+mod db_connect_info;
+mod heartbeat;
 mod initial_request;
 mod params;
 mod session_state;
 mod tcp_client;
+mod transport;
 
 pub(crate) use am_conn_core::AmConnCore;
+pub use cancel_handle::CancelHandle;
+pub(crate) use cancel_handle::CancelState;
 pub(crate) use connection_core::ConnectionCore;
+use heartbeat::Heartbeat;
+pub(crate) use params::proxy_params::ProxyParams;
 pub use params::{
-    connect_params::{ConnectParams, ServerCerts},
+    connect_params::{ClientCerts, ConnectParams, ServerCerts},
     connect_params_builder::ConnectParamsBuilder,
     into_connect_params::IntoConnectParams,
     into_connect_params_builder::IntoConnectParamsBuilder,
+    proxy_params::ProxyType,
+    server_cert_verifier::ServerCertVerifier,
 };
 use session_state::SessionState;
+#[cfg(feature = "async")]
+use tcp_client::AsyncChannel;
 use tcp_client::TcpClient;
+pub(crate) use transport::AmTransportFactory;
+pub use transport::{Transport, TransportFactory};
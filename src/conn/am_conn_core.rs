@@ -1,16 +1,22 @@
-use crate::conn::ConnectionCore;
-use crate::protocol::parts::{ResultSetMetadata, RsState};
+#[cfg(feature = "async")]
+use crate::conn::connection_core::ReconnectRetry;
+#[cfg(feature = "async")]
+use crate::conn::{authentication, db_connect_info, initial_request, AsyncChannel, TcpClient};
+use crate::conn::{ConnectionCore, Heartbeat};
+use crate::protocol::parts::{HostDescriptor, ResultSetMetadata, RsState, SiteType};
 use crate::protocol::{Reply, Request};
 use crate::{ConnectParams, HdbError, HdbResult, ParameterDescriptors};
 use chrono::Local;
-use std::sync::{Arc, LockResult, Mutex, MutexGuard};
+use std::sync::{Arc, LockResult, Mutex, MutexGuard, Weak};
+#[cfg(feature = "async")]
+use std::time::Duration;
 
 // A thread-safe encapsulation of the ConnectionCore.
 #[derive(Debug)]
 pub(crate) struct AmConnCore(Arc<Mutex<ConnectionCore>>);
 
 impl AmConnCore {
-    pub fn try_new(conn_params: ConnectParams) -> HdbResult<Self> {
+    pub fn try_new(conn_params: &ConnectParams) -> HdbResult<Self> {
         trace!("trying to connect to {}", conn_params);
         let start = Local::now();
         let conn_core = ConnectionCore::try_new(conn_params)?;
@@ -27,17 +33,244 @@ impl AmConnCore {
                 conn_core.connect_options().get_full_version_string()
             );
         }
-        Ok(Self(Arc::new(Mutex::new(conn_core))))
+        let o_heartbeat_interval = conn_core.connect_params().heartbeat_interval();
+        let am_conn_core = Self(Arc::new(Mutex::new(conn_core)));
+        if let Some(interval) = o_heartbeat_interval {
+            let heartbeat = Heartbeat::start(&am_conn_core, interval)?;
+            am_conn_core.lock()?.set_heartbeat(heartbeat);
+        }
+        Ok(am_conn_core)
+    }
+
+    // Connects and authenticates over a non-blocking connection.
+    #[cfg(feature = "async")]
+    pub async fn try_new_async(conn_params: ConnectParams) -> HdbResult<Self> {
+        trace!("trying to connect asynchronously to {}", conn_params);
+        if conn_params.has_transport_factory() {
+            return Err(HdbError::Usage(
+                "A TransportFactory can only be used with synchronous connections",
+            ));
+        }
+        let start = Local::now();
+        let conn_core = ConnectionCore::try_new_unauthenticated_async(&conn_params).await?;
+        let mut am_conn_core = Self(Arc::new(Mutex::new(conn_core)));
+        if let Some(database_name) = conn_params.database_name() {
+            if let Some((host, port)) =
+                db_connect_info::redirect_async(&am_conn_core, database_name).await?
+            {
+                let conn_core = ConnectionCore::try_new_unauthenticated_async(
+                    &conn_params.for_tenant_host(&host, port),
+                )
+                .await?;
+                am_conn_core = Self(Arc::new(Mutex::new(conn_core)));
+            }
+        }
+        authentication::authenticate_async(&am_conn_core, false).await?;
+        let o_stmt = am_conn_core.lock()?.initial_schema_statement();
+        if let Some(stmt) = o_stmt {
+            let request = ConnectionCore::restore_request(&stmt);
+            am_conn_core
+                .roundtrip_async(&request, None, None, None)
+                .await?;
+        }
+        {
+            let conn_core = am_conn_core.lock()?;
+            debug!(
+                "user \"{}\" successfully logged on ({} µs) to {:?} of {:?} (HANA version: {:?})",
+                conn_core.connect_params().dbuser(),
+                Local::now()
+                    .signed_duration_since(start)
+                    .num_microseconds()
+                    .unwrap_or(-1),
+                conn_core.connect_options().get_database_name(),
+                conn_core.connect_options().get_system_id(),
+                conn_core.connect_options().get_full_version_string()
+            );
+        }
+        let o_heartbeat_interval = am_conn_core.lock()?.connect_params().heartbeat_interval();
+        if let Some(interval) = o_heartbeat_interval {
+            if let Some(heartbeat) = Heartbeat::start_async(&am_conn_core, interval) {
+                am_conn_core.lock()?.set_heartbeat(heartbeat);
+            }
+        }
+        Ok(am_conn_core)
     }
 
     pub fn lock(&self) -> LockResult<MutexGuard<ConnectionCore>> {
         self.0.lock()
     }
 
+    // A reference that does not keep the connection alive.
+    pub fn downgrade(&self) -> Weak<Mutex<ConnectionCore>> {
+        Arc::downgrade(&self.0)
+    }
+
+    pub fn upgrade(weak_core: &Weak<Mutex<ConnectionCore>>) -> Option<Self> {
+        weak_core.upgrade().map(Self)
+    }
+
     pub fn send_sync(&mut self, request: Request) -> HdbResult<Reply> {
         self.full_send_sync(request, None, None, &mut None)
     }
 
+    // Returns the secondary connection to which a statement on a table with the given
+    // location should be routed, or None if it is to be executed on this connection.
+    //
+    // A missing secondary connection is opened here; if that fails, the statement
+    // is executed on this connection.
+    // Statements are only routed in auto-commit mode, so that transactions stay
+    // in a single session.
+    pub fn routing_target(&self, table_location: &[i32]) -> HdbResult<Option<Self>> {
+        let (volume_id, params) = {
+            let conn_core = self.lock()?;
+            if !conn_core.is_statement_routing_active() || !conn_core.is_auto_commit() {
+                return Ok(None);
+            }
+            let hosts = conn_core.topology();
+            let o_current_volume_id = hosts
+                .iter()
+                .find(|host| host.is_current_session())
+                .and_then(HostDescriptor::volume_id);
+            let volume_id = match (o_current_volume_id, table_location.first()) {
+                (Some(current), Some(volume_id)) if !table_location.contains(&current) => {
+                    *volume_id
+                }
+                _ => return Ok(None),
+            };
+            if let Some(secondary) = conn_core.secondary(volume_id) {
+                conn_core.align_session_of(secondary)?;
+                return Ok(Some(secondary.clone()));
+            }
+            if let Some(host) = hosts
+                .iter()
+                .find(|host| host.volume_id() == Some(volume_id))
+            {
+                (
+                    volume_id,
+                    conn_core
+                        .connect_params()
+                        .for_secondary_host(host.host(), host.port()),
+                )
+            } else {
+                warn!("No host found in the topology for volume {}", volume_id);
+                return Ok(None);
+            }
+        };
+
+        debug!(
+            "Opening secondary connection to {} for statement routing",
+            params.addr()
+        );
+        match Self::try_new(&params) {
+            Ok(secondary) => Ok(Some(self.lock()?.add_secondary(volume_id, secondary)?)),
+            Err(e) => {
+                warn!(
+                    "Secondary connection to {} failed, statement is not routed: {}",
+                    params.addr(),
+                    e
+                );
+                Ok(None)
+            }
+        }
+    }
+
+    // Returns the companion connection to the secondary site of an Active/Active system,
+    // to which read-only statements should be routed, or None if they are to be executed
+    // on this connection.
+    //
+    // A missing or broken companion connection is (re-)opened here; if that fails,
+    // the statement is executed on this connection, and no further attempt is made
+    // for a while.
+    pub fn read_only_target(&self) -> HdbResult<Option<Self>> {
+        let params = {
+            let mut conn_core = self.lock()?;
+            if !conn_core.is_read_only_routing_active() {
+                return Ok(None);
+            }
+            if let Some(companion) = conn_core.read_only_companion() {
+                if !companion.lock()?.is_broken() {
+                    conn_core.align_session_of(companion)?;
+                    return Ok(Some(companion.clone()));
+                }
+                debug!("Companion connection for read-only routing is broken");
+                conn_core.drop_read_only_companion();
+            }
+            if !conn_core.is_read_only_companion_due() {
+                trace!("Companion connection failed recently, read-only routing is skipped");
+                return Ok(None);
+            }
+            let hosts = conn_core.topology();
+            let o_host = hosts
+                .iter()
+                .filter(|host| host.site_type() == SiteType::Secondary && !host.is_standby())
+                .max_by_key(|host| host.is_master());
+            if let Some(host) = o_host {
+                conn_core
+                    .connect_params()
+                    .for_secondary_host(host.host(), host.port())
+            } else {
+                debug!("No secondary site found in the topology, read-only routing is skipped");
+                return Ok(None);
+            }
+        };
+
+        debug!(
+            "Opening companion connection to {} for read-only routing",
+            params.addr()
+        );
+        match Self::try_new(&params) {
+            Ok(companion) => {
+                debug!(
+                    "Companion connection was opened on site {:?}",
+                    companion
+                        .lock()?
+                        .connect_options()
+                        .get_active_active_conn_origin_site()
+                );
+                Ok(Some(self.lock()?.set_read_only_companion(companion)?))
+            }
+            Err(e) => {
+                warn!(
+                    "Companion connection to {} failed, statement is not routed: {}",
+                    params.addr(),
+                    e
+                );
+                self.lock()?.set_read_only_companion_failed();
+                Ok(None)
+            }
+        }
+    }
+
+    // Used in Drop implementations, where errors can only be ignored.
+    //
+    // With a non-blocking connection we cannot wait for the reply here,
+    // so the roundtrip is handed over to the async runtime, if there is one.
+    pub fn send_and_ignore_errors(&mut self, request: Request<'static>) {
+        #[cfg(feature = "async")]
+        {
+            let is_async = self
+                .lock()
+                .is_ok_and(|conn_core| conn_core.async_channel().is_ok());
+            if is_async {
+                match tokio::runtime::Handle::try_current() {
+                    Ok(handle) => {
+                        let am_conn_core = self.clone();
+                        handle.spawn(async move {
+                            if let Err(e) = am_conn_core.send_async(request).await {
+                                trace!("send_and_ignore_errors(): ignoring {:?}", e);
+                            }
+                        });
+                    }
+                    Err(e) => trace!("send_and_ignore_errors(): no runtime available: {}", e),
+                }
+                return;
+            }
+        }
+        if let Err(e) = self.send_sync(request) {
+            trace!("send_and_ignore_errors(): ignoring {:?}", e);
+        }
+    }
+
     pub fn full_send_sync(
         &mut self,
         mut request: Request,
@@ -51,12 +284,78 @@ impl AmConnCore {
         );
         let start = Local::now();
         let mut conn_core = self.lock()?;
+        conn_core.take_pending_transaction_lost(&request)?;
         conn_core.augment_request(&mut request);
 
-        match conn_core.roundtrip_sync(&request, Some(&self), o_a_rsmd, o_a_descriptors, o_rs) {
+        let result =
+            match conn_core.roundtrip_sync(&request, Some(self), o_a_rsmd, o_a_descriptors, o_rs) {
+                Ok(reply) => {
+                    trace!(
+                        "full_send_sync() took {} ms",
+                        (Local::now().signed_duration_since(start)).num_milliseconds()
+                    );
+                    Ok(reply)
+                }
+                Err(HdbError::Tcp { source })
+                    if std::io::ErrorKind::ConnectionReset == source.kind() =>
+                {
+                    debug!("full_send_sync(): reconnecting after ConnectionReset error...");
+                    // fails with TransactionLost if the request must not be repeated
+                    conn_core.reconnect().and_then(|()| {
+                        debug!("full_send_sync(): repeating request after reconnect...");
+                        conn_core.roundtrip_sync(
+                            &request,
+                            Some(self),
+                            o_a_rsmd,
+                            o_a_descriptors,
+                            o_rs,
+                        )
+                    })
+                }
+                Err(e) => Err(e),
+            };
+        result.map_err(|e| conn_core.track_error(e))
+    }
+}
+
+#[cfg(feature = "async")]
+impl AmConnCore {
+    pub async fn send_async(&self, request: Request<'_>) -> HdbResult<Reply> {
+        self.full_send_async(request, None, None, None).await
+    }
+
+    pub async fn full_send_async(
+        &self,
+        mut request: Request<'_>,
+        o_a_rsmd: Option<&Arc<ResultSetMetadata>>,
+        o_a_descriptors: Option<&Arc<ParameterDescriptors>>,
+        o_am_rs_state: Option<&Mutex<RsState>>,
+    ) -> HdbResult<Reply> {
+        trace!(
+            "AmConnCore::full_send_async() with requestType = {:?}",
+            request.request_type,
+        );
+        let start = Local::now();
+        let session_gate = self.lock()?.session_gate();
+        let (session_generation, result) = {
+            let _shared = session_gate.read().await;
+            let session_generation = {
+                let mut conn_core = self.lock()?;
+                conn_core.take_pending_transaction_lost(&request)?;
+                conn_core.augment_request(&mut request);
+                conn_core.session_generation()
+            };
+            (
+                session_generation,
+                self.roundtrip_async(&request, o_a_rsmd, o_a_descriptors, o_am_rs_state)
+                    .await,
+            )
+        };
+
+        let result = match result {
             Ok(reply) => {
                 trace!(
-                    "full_send_sync() took {} ms",
+                    "full_send_async() took {} ms",
                     (Local::now().signed_duration_since(start)).num_milliseconds()
                 );
                 Ok(reply)
@@ -64,12 +363,197 @@ impl AmConnCore {
             Err(HdbError::Tcp { source })
                 if std::io::ErrorKind::ConnectionReset == source.kind() =>
             {
-                debug!("full_send_sync(): reconnecting after ConnectionReset error...");
-                conn_core.reconnect()?;
-                debug!("full_send_sync(): repeating request after reconnect...");
-                conn_core.roundtrip_sync(&request, Some(&self), o_a_rsmd, o_a_descriptors, o_rs)
+                debug!("full_send_async(): reconnecting after ConnectionReset error...");
+                // fails with TransactionLost if the request must not be repeated
+                match self
+                    .reconnect_async(&session_gate, session_generation)
+                    .await
+                {
+                    Ok(()) => {
+                        debug!("full_send_async(): repeating request after reconnect...");
+                        let _shared = session_gate.read().await;
+                        self.roundtrip_async(&request, o_a_rsmd, o_a_descriptors, o_am_rs_state)
+                            .await
+                    }
+                    Err(e) => Err(e),
+                }
+            }
+            Err(e) => Err(e),
+        };
+        match result {
+            Err(e) => Err(self.lock()?.track_error(e)),
+            ok => ok,
+        }
+    }
+
+    // The lock on the ConnectionCore is never held across an await point;
+    // the channel has its own lock, which serializes the roundtrips.
+    //
+    // Except for opening and re-establishing the session, which have exclusive access,
+    // the caller must hold the session gate, see full_send_async().
+    pub(crate) async fn roundtrip_async(
+        &self,
+        request: &Request<'_>,
+        o_a_rsmd: Option<&Arc<ResultSetMetadata>>,
+        o_a_descriptors: Option<&Arc<ParameterDescriptors>>,
+        o_am_rs_state: Option<&Mutex<RsState>>,
+    ) -> HdbResult<Reply> {
+        let am_channel = self.lock()?.async_channel()?;
+        let (roundtrip, buf) = {
+            let channel = am_channel.lock().await;
+            let (roundtrip, buf) = self.lock()?.emit_for_async(request, o_a_descriptors)?;
+            let pending_reply = PendingReply {
+                am_conn_core: self,
+                channel,
+                armed: true,
+            };
+            (roundtrip, pending_reply.exchange(&buf).await?)
+        };
+
+        if let Some(am_rs_state) = o_am_rs_state {
+            let mut rs_state = am_rs_state.lock()?;
+            self.lock()?.parse_from_async(
+                request,
+                roundtrip,
+                &buf,
+                Some(self),
+                o_a_rsmd,
+                o_a_descriptors,
+                &mut Some(&mut *rs_state),
+            )
+        } else {
+            self.lock()?.parse_from_async(
+                request,
+                roundtrip,
+                &buf,
+                Some(self),
+                o_a_rsmd,
+                o_a_descriptors,
+                &mut None,
+            )
+        }
+    }
+
+    // If the reconnect fails, the connection is marked as broken.
+    //
+    // The session gate is held exclusively during the whole reconnect. Tasks whose
+    // requests failed in the same session find the connection already re-established
+    // when they get the gate.
+    async fn reconnect_async(
+        &self,
+        session_gate: &tokio::sync::RwLock<()>,
+        failed_session_generation: u64,
+    ) -> HdbResult<()> {
+        let _exclusive = session_gate.write().await;
+        {
+            let conn_core = self.lock()?;
+            if conn_core.session_generation() != failed_session_generation {
+                debug!("Connection was already re-established by another task");
+                return if conn_core.is_transaction_lost_by_reconnect() {
+                    Err(HdbError::TransactionLost)
+                } else {
+                    Ok(())
+                };
+            }
+        }
+        match self.try_reconnect_async().await {
+            Err(e) => Err(self.lock()?.track_reconnect_error(e)),
+            ok => ok,
+        }
+    }
+
+    async fn try_reconnect_async(&self) -> HdbResult<()> {
+        debug!("Trying to reconnect");
+        let (conn_params, wait_timeout) = {
+            let conn_core = self.lock()?;
+            (
+                conn_core.connect_params().for_database_lookup(),
+                conn_core.reconnect_wait_timeout(),
+            )
+        };
+        let tcp_conn = open_for_reconnect_async(&conn_params, wait_timeout).await?;
+        let transaction_lost = self.lock()?.set_unauthenticated_tcp_conn(tcp_conn);
+        if let Some(database_name) = conn_params.database_name() {
+            if let Some((host, port)) = db_connect_info::redirect_async(self, database_name).await?
+            {
+                let tcp_conn =
+                    TcpClient::try_new_async(&conn_params.for_tenant_host(&host, port)).await?;
+                initial_request::send_and_receive_async(&tcp_conn).await?;
+                self.lock()?.set_redirected_tcp_conn(tcp_conn);
+            }
+        }
+        debug!("Successfully reconnected, not yet authenticated");
+        authentication::authenticate_async(self, true).await?;
+        debug!("Successfully re-authenticated");
+        let statements = self.lock()?.session_restore_statements();
+        for stmt in statements {
+            let request = ConnectionCore::restore_request(&stmt);
+            self.roundtrip_async(&request, None, None, None).await?;
+        }
+        debug!("Successfully restored the session state");
+        if transaction_lost {
+            Err(HdbError::TransactionLost)
+        } else {
+            Ok(())
+        }
+    }
+}
+
+// Guards a roundtrip over a non-blocking connection from sending the request until
+// the reply is read completely.
+//
+// If the roundtrip is abandoned in between, because its future is dropped (e.g. by
+// tokio::time::timeout() or select!) or because of an io error, the reply would be taken
+// as the reply to the next request. The channel is then marked as out of sync, so that
+// the next request re-establishes the connection, and the connection as broken.
+#[cfg(feature = "async")]
+struct PendingReply<'a> {
+    am_conn_core: &'a AmConnCore,
+    channel: tokio::sync::MutexGuard<'a, AsyncChannel>,
+    armed: bool,
+}
+#[cfg(feature = "async")]
+impl PendingReply<'_> {
+    async fn exchange(mut self, request: &[u8]) -> std::io::Result<Vec<u8>> {
+        self.channel.write_all(request).await?;
+        let reply = self.channel.read_message().await?;
+        self.armed = false;
+        Ok(reply)
+    }
+}
+#[cfg(feature = "async")]
+impl Drop for PendingReply<'_> {
+    fn drop(&mut self) {
+        if self.armed {
+            warn!("Roundtrip was interrupted, the connection needs to be re-established");
+            self.channel.set_out_of_sync();
+            if let Ok(mut conn_core) = self.am_conn_core.lock() {
+                conn_core.set_broken();
             }
+        }
+    }
+}
+
+// Like ConnectionCore::open_for_reconnect, for non-blocking connections.
+#[cfg(feature = "async")]
+async fn open_for_reconnect_async(
+    params: &ConnectParams,
+    wait_timeout: Option<Duration>,
+) -> std::io::Result<TcpClient> {
+    let retry = ReconnectRetry::new(params, wait_timeout);
+    loop {
+        let result = match TcpClient::try_new_async(params).await {
+            Ok(tcp_conn) => initial_request::send_and_receive_async(&tcp_conn)
+                .await
+                .map(|()| tcp_conn),
             Err(e) => Err(e),
+        };
+        match result {
+            Err(e) => match retry.delay_before_next_attempt(&e) {
+                Some(delay) => tokio::time::sleep(delay).await,
+                None => return Err(e),
+            },
+            ok => return ok,
         }
     }
 }
@@ -1,13 +1,22 @@
 use super::Authenticator;
 use crate::conn::ConnectionCore;
 use crate::protocol::parts::{AuthFields, ClientContext, ConnOptId, OptionValue};
-use crate::protocol::{Part, ReplyType, Request, RequestType};
+use crate::protocol::{Part, Reply, ReplyType, Request, RequestType};
 use crate::{HdbError, HdbResult};
 
 pub(crate) fn first_auth_request(
     conn_core: &mut ConnectionCore,
     authenticators: &[Box<dyn Authenticator>],
 ) -> HdbResult<(String, Vec<u8>)> {
+    let request1 = build_first_auth_request(conn_core, authenticators);
+    let reply = conn_core.roundtrip_sync(&request1, None, None, None, &mut None)?;
+    evaluate_first_auth_reply(reply)
+}
+
+pub(crate) fn build_first_auth_request<'a>(
+    conn_core: &ConnectionCore,
+    authenticators: &[Box<dyn Authenticator>],
+) -> Request<'a> {
     let mut request1 = Request::new(RequestType::Authenticate, 0);
     request1.push(Part::ClientContext(ClientContext::new()));
 
@@ -19,8 +28,10 @@ pub(crate) fn first_auth_request(
         auth_fields.push(authenticator.client_challenge().to_vec());
     }
     request1.push(Part::Auth(auth_fields));
+    request1
+}
 
-    let reply = conn_core.roundtrip_sync(&request1, None, None, None, &mut None)?;
+pub(crate) fn evaluate_first_auth_reply(reply: Reply) -> HdbResult<(String, Vec<u8>)> {
     reply.assert_expected_reply_type(ReplyType::Nil)?;
 
     let mut result = None;
@@ -46,6 +57,22 @@ pub(crate) fn second_auth_request(
     server_challenge_data: &[u8],
     reconnect: bool,
 ) -> HdbResult<()> {
+    let request2 = build_second_auth_request(
+        conn_core,
+        chosen_authenticator.as_mut(),
+        server_challenge_data,
+        reconnect,
+    )?;
+    let reply = conn_core.roundtrip_sync(&request2, None, None, None, &mut None)?;
+    evaluate_second_auth_reply(conn_core, chosen_authenticator.as_ref(), reply)
+}
+
+pub(crate) fn build_second_auth_request<'a>(
+    conn_core: &ConnectionCore,
+    chosen_authenticator: &mut dyn Authenticator,
+    server_challenge_data: &[u8],
+    reconnect: bool,
+) -> HdbResult<Request<'a>> {
     let mut request2 = Request::new(RequestType::Connect, 0);
 
     debug!("authenticating with {}", chosen_authenticator.name());
@@ -69,15 +96,20 @@ pub(crate) fn second_auth_request(
         );
     }
     request2.push(Part::ConnectOptions(conn_opts));
+    Ok(request2)
+}
 
-    let reply = conn_core.roundtrip_sync(&request2, None, None, None, &mut None)?;
+pub(crate) fn evaluate_second_auth_reply(
+    conn_core: &mut ConnectionCore,
+    chosen_authenticator: &dyn Authenticator,
+    reply: Reply,
+) -> HdbResult<()> {
     reply.assert_expected_reply_type(ReplyType::Nil)?;
 
     conn_core.set_session_id(reply.session_id());
 
     for part in reply.parts.into_iter() {
         match part {
-            Part::TopologyInformation(topology) => conn_core.set_topology(topology),
             Part::ConnectOptions(received_co) => conn_core
                 .connect_options_mut()
                 .digest_server_connect_options(received_co)?,
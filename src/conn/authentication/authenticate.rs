@@ -1,6 +1,13 @@
+#[cfg(feature = "async")]
+use super::auth_requests::{
+    build_first_auth_request, build_second_auth_request, evaluate_first_auth_reply,
+    evaluate_second_auth_reply,
+};
 use super::{
     first_auth_request, second_auth_request, Authenticator, ScramPbkdf2Sha256, ScramSha256,
 };
+#[cfg(feature = "async")]
+use crate::conn::AmConnCore;
 use crate::conn::ConnectionCore;
 use crate::hdb_error::{HdbError, HdbResult};
 
@@ -35,3 +42,43 @@ pub(crate) fn authenticate(conn_core: &mut ConnectionCore, reconnect: bool) -> H
     trace!("session_id: {}", conn_core.session_id());
     Ok(())
 }
+
+// Does the authentication over a non-blocking connection.
+#[cfg(feature = "async")]
+pub(crate) async fn authenticate_async(
+    am_conn_core: &AmConnCore,
+    reconnect: bool,
+) -> HdbResult<()> {
+    trace!("authenticate_async()");
+    let authenticators: Vec<Box<dyn Authenticator>> = vec![
+        ScramSha256::boxed_authenticator(),
+        ScramPbkdf2Sha256::boxed_authenticator(),
+    ];
+
+    let request1 = build_first_auth_request(&*am_conn_core.lock()?, &authenticators);
+    let reply = am_conn_core
+        .roundtrip_async(&request1, None, None, None)
+        .await?;
+    let (selected, server_challenge) = evaluate_first_auth_reply(reply)?;
+
+    let mut authenticator: Box<dyn Authenticator> = authenticators
+        .into_iter()
+        .find(|authenticator| authenticator.name() == selected)
+        .ok_or_else(|| HdbError::Impl("None of the available authenticators was accepted"))?;
+
+    let request2 = build_second_auth_request(
+        &*am_conn_core.lock()?,
+        authenticator.as_mut(),
+        &server_challenge,
+        reconnect,
+    )?;
+    let reply = am_conn_core
+        .roundtrip_async(&request2, None, None, None)
+        .await?;
+
+    let mut conn_core = am_conn_core.lock()?;
+    evaluate_second_auth_reply(&mut conn_core, authenticator.as_ref(), reply)?;
+    conn_core.set_authenticated();
+    trace!("session_id: {}", conn_core.session_id());
+    Ok(())
+}
@@ -1,7 +1,7 @@
 use crate::{HdbError, HdbResult};
 use secstr::SecStr;
 
-pub trait Authenticator {
+pub trait Authenticator: Send {
     fn name(&self) -> &str;
 
     fn name_as_bytes(&self) -> Vec<u8>;
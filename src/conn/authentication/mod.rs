@@ -7,6 +7,8 @@ mod scram_sha256;
 
 pub(super) use self::auth_requests::{first_auth_request, second_auth_request};
 pub(super) use self::authenticate::authenticate;
+#[cfg(feature = "async")]
+pub(super) use self::authenticate::authenticate_async;
 pub(super) use self::authenticator::Authenticator;
 pub(super) use self::scram_pbkdf2_sha256::ScramPbkdf2Sha256;
 pub(super) use self::scram_sha256::ScramSha256;
@@ -0,0 +1,70 @@
+// In a multitenant database container, the system database tells where a tenant
+// database can be reached. The lookup is done before the authentication.
+#[cfg(feature = "async")]
+use super::AmConnCore;
+use super::ConnectionCore;
+use crate::protocol::parts::DbConnectInfo;
+use crate::protocol::{Part, Reply, Request, RequestType};
+use crate::{HdbError, HdbResult};
+
+// Returns the host and port of the requested database,
+// or None if the connection is already to that database.
+pub(crate) fn redirect(
+    conn_core: &mut ConnectionCore,
+    database_name: &str,
+) -> HdbResult<Option<(String, u16)>> {
+    let request = build_request(database_name);
+    let reply = conn_core.roundtrip_sync(&request, None, None, None, &mut None)?;
+    evaluate_reply(reply, database_name)
+}
+
+#[cfg(feature = "async")]
+pub(crate) async fn redirect_async(
+    am_conn_core: &AmConnCore,
+    database_name: &str,
+) -> HdbResult<Option<(String, u16)>> {
+    let request = build_request(database_name);
+    let reply = am_conn_core
+        .roundtrip_async(&request, None, None, None)
+        .await?;
+    evaluate_reply(reply, database_name)
+}
+
+fn build_request(database_name: &str) -> Request<'static> {
+    let mut request = Request::new(RequestType::DbConnectInfo, 0);
+    request.push(Part::DbConnectInfo(DbConnectInfo::new(database_name)));
+    request
+}
+
+fn evaluate_reply(reply: Reply, database_name: &str) -> HdbResult<Option<(String, u16)>> {
+    let db_connect_info = reply
+        .parts
+        .into_iter()
+        .find_map(|part| match part {
+            Part::DbConnectInfo(db_connect_info) => Some(db_connect_info),
+            part => {
+                warn!(
+                    "db_connect_info: ignoring unexpected part of kind {:?}",
+                    part.kind()
+                );
+                None
+            }
+        })
+        .ok_or(HdbError::Impl("No DbConnectInfo received"))?;
+
+    if db_connect_info.is_connected() {
+        debug!("Connected to database {}", database_name);
+        Ok(None)
+    } else if let (Some(host), Some(port)) = (db_connect_info.host(), db_connect_info.port()) {
+        debug!(
+            "Database {} can be reached at {}:{}",
+            database_name, host, port
+        );
+        Ok(Some((host.to_string(), port)))
+    } else {
+        Err(HdbError::UsageDetailed(format!(
+            "No host and port received for database {}",
+            database_name
+        )))
+    }
+}
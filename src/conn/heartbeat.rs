@@ -0,0 +1,147 @@
+// The heartbeat pings connections that were idle for the configured interval,
+// to keep the session alive and to detect broken connections early.
+//
+// The heartbeat only holds a weak reference to the connection; it is stopped when
+// the ConnectionCore, which owns the Heartbeat, is dropped.
+use crate::conn::{AmConnCore, ConnectionCore};
+use crate::protocol::{Request, RequestType};
+use crate::{HdbError, HdbResult};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::{Mutex, TryLockError, Weak};
+use std::time::Duration;
+
+#[derive(Debug)]
+pub(crate) enum Heartbeat {
+    // The heartbeat thread of a blocking connection ends when the sender is dropped.
+    Thread {
+        _stop: mpsc::Sender<()>,
+    },
+    // The heartbeat task of a non-blocking connection ends when the sender is dropped.
+    #[cfg(feature = "async")]
+    Task {
+        _stop: tokio::sync::oneshot::Sender<()>,
+    },
+}
+impl Heartbeat {
+    // Starts a thread that pings the blocking connection when it was idle for `interval`.
+    pub fn start(am_conn_core: &AmConnCore, interval: Duration) -> std::io::Result<Self> {
+        let (sender, receiver) = mpsc::channel::<()>();
+        let weak_core = am_conn_core.downgrade();
+        std::thread::Builder::new()
+            .name("hdbconnect-heartbeat".to_string())
+            .spawn(move || {
+                let mut wait = interval;
+                // recv_timeout() returns Disconnected when the sender is dropped
+                while let Err(RecvTimeoutError::Timeout) = receiver.recv_timeout(wait) {
+                    wait = match state(&weak_core) {
+                        State::Gone => break,
+                        State::Busy => interval,
+                        State::Idle(idle) if idle < interval => interval.saturating_sub(idle),
+                        State::Idle(_) => {
+                            if let Some(mut am_conn_core) = AmConnCore::upgrade(&weak_core) {
+                                if let Err(e) = ping_sync(&mut am_conn_core) {
+                                    warn!("heartbeat: ping failed with {}", e);
+                                }
+                            }
+                            interval
+                        }
+                    };
+                }
+                trace!("heartbeat thread ends");
+            })?;
+        Ok(Self::Thread { _stop: sender })
+    }
+
+    // Spawns a task that pings the non-blocking connection when it was idle for `interval`.
+    //
+    // Returns None if there is no tokio runtime to spawn the task on.
+    #[cfg(feature = "async")]
+    pub fn start_async(am_conn_core: &AmConnCore, interval: Duration) -> Option<Self> {
+        let handle = match tokio::runtime::Handle::try_current() {
+            Ok(handle) => handle,
+            Err(e) => {
+                warn!("heartbeat not started, no runtime available: {}", e);
+                return None;
+            }
+        };
+        let (sender, mut receiver) = tokio::sync::oneshot::channel::<()>();
+        let weak_core = am_conn_core.downgrade();
+        handle.spawn(async move {
+            let mut wait = interval;
+            // the receiver resolves when the sender is dropped, the timeout when it is not
+            while tokio::time::timeout(wait, &mut receiver).await.is_err() {
+                wait = match state(&weak_core) {
+                    State::Gone => break,
+                    State::Busy => interval,
+                    State::Idle(idle) if idle < interval => interval.saturating_sub(idle),
+                    State::Idle(_) => {
+                        if let Some(am_conn_core) = AmConnCore::upgrade(&weak_core) {
+                            if let Err(e) = ping_async(&am_conn_core).await {
+                                warn!("heartbeat: ping failed with {}", e);
+                            }
+                        }
+                        interval
+                    }
+                };
+            }
+            trace!("heartbeat task ends");
+        });
+        Some(Self::Task { _stop: sender })
+    }
+}
+
+enum State {
+    // the connection was dropped
+    Gone,
+    // the connection is currently in use
+    Busy,
+    // the connection was not used for the given time
+    Idle(Duration),
+}
+
+fn state(weak_core: &Weak<Mutex<ConnectionCore>>) -> State {
+    match weak_core.upgrade() {
+        None => State::Gone,
+        Some(am_core) => match am_core.try_lock() {
+            Ok(ref conn_core) if conn_core.is_busy() || conn_core.is_heartbeat_suspended() => {
+                State::Busy
+            }
+            Ok(conn_core) => State::Idle(conn_core.idle_time()),
+            Err(TryLockError::WouldBlock) => State::Busy,
+            Err(TryLockError::Poisoned(_)) => State::Gone,
+        },
+    }
+}
+
+// The ping executes no SQL, so it is not affected by the query timeout, and it does not
+// send pending changes of the client info or the session variables.
+//
+// A transaction that got lost because the ping re-established the session
+// is reported to the application with its next request.
+fn ping_sync(am_conn_core: &mut AmConnCore) -> HdbResult<()> {
+    trace!("heartbeat: pinging idle connection");
+    match am_conn_core.send_sync(Request::new(RequestType::Ping, 0)) {
+        Ok(_) => Ok(()),
+        Err(HdbError::TransactionLost) => {
+            am_conn_core.lock()?.set_pending_transaction_lost();
+            Err(HdbError::TransactionLost)
+        }
+        Err(e) => Err(e),
+    }
+}
+
+#[cfg(feature = "async")]
+async fn ping_async(am_conn_core: &AmConnCore) -> HdbResult<()> {
+    trace!("heartbeat: pinging idle connection");
+    match am_conn_core
+        .send_async(Request::new(RequestType::Ping, 0))
+        .await
+    {
+        Ok(_) => Ok(()),
+        Err(HdbError::TransactionLost) => {
+            am_conn_core.lock()?.set_pending_transaction_lost();
+            Err(HdbError::TransactionLost)
+        }
+        Err(e) => Err(e),
+    }
+}
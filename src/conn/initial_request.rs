@@ -5,25 +5,41 @@ use std::io::Write;
 
 pub(crate) fn send_and_receive(tcp_conn: &mut TcpClient) -> std::io::Result<()> {
     trace!("send_and_receive(): send");
-    match tcp_conn {
-        TcpClient::SyncPlain(ref mut pc) => {
-            emit_initial_request(pc.writer())?;
-        }
-        TcpClient::SyncTls(ref mut tc) => {
-            emit_initial_request(tc.writer())?;
-        }
-    }
+    let sync_client = tcp_conn.sync_client().ok_or_else(|| {
+        util::io_error("Synchronous initial request not possible on an asynchronous connection")
+    })?;
+    emit_initial_request(sync_client.writer())?;
 
     trace!("send_and_receive(): receive");
-    match tcp_conn {
-        TcpClient::SyncPlain(ref mut pc) => {
-            util::skip_bytes(8, pc.reader()) // ignore the response content
-        }
-        TcpClient::SyncTls(ref mut tc) => {
-            util::skip_bytes(8, tc.reader()) // ignore the response content
+    util::skip_bytes(8, sync_client.reader()) // ignore the response content
+        .map_err(|e| {
+            trace!("Skipping over empty initial response failed with {:?}", e);
+            e
+        })?;
+    debug!("Successfully initialized");
+    Ok(())
+}
+
+#[cfg(feature = "async")]
+pub(crate) async fn send_and_receive_async(tcp_conn: &TcpClient) -> std::io::Result<()> {
+    let am_channel = match tcp_conn {
+        TcpClient::Async(ref ac) => ac.am_channel(),
+        TcpClient::Sync(_) => {
+            return Err(util::io_error(
+                "Asynchronous initial request not possible on a synchronous connection",
+            ))
         }
-    }
-    .map_err(|e| {
+    };
+    let mut channel = am_channel.lock().await;
+
+    trace!("send_and_receive_async(): send");
+    let mut buf = Vec::<u8>::with_capacity(14);
+    emit_initial_request(&mut buf)?;
+    channel.write_all(&buf).await?;
+
+    trace!("send_and_receive_async(): receive");
+    let mut response = [0_u8; 8]; // ignore the response content
+    channel.read_exact(&mut response).await.map_err(|e| {
         trace!("Skipping over empty initial response failed with {:?}", e);
         e
     })?;
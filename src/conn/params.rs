@@ -1,5 +1,13 @@
+// Without a TLS implementation, the TLS settings are accepted, but not used.
+#[cfg_attr(not(any(feature = "rustls", feature = "native-tls")), allow(dead_code))]
 pub mod connect_params;
 pub mod connect_params_builder;
 mod cp_url;
 pub mod into_connect_params;
 pub mod into_connect_params_builder;
+#[cfg_attr(not(any(feature = "rustls", feature = "native-tls")), allow(dead_code))]
+mod pkcs12;
+pub mod proxy_params;
+#[cfg_attr(not(any(feature = "rustls", feature = "native-tls")), allow(dead_code))]
+pub mod server_cert_verifier;
+mod service_binding;
@@ -1,5 +1,12 @@
-use crate::{ConnectParams, HdbError, HdbResult, IntoConnectParamsBuilder, ServerCerts};
+use super::server_cert_verifier::{parse_public_key_pin, AmServerCertVerifier};
+use crate::conn::{AmTransportFactory, ProxyParams};
+use crate::{
+    ClientCerts, ConnectParams, HdbError, HdbResult, IntoConnectParamsBuilder, ProxyType,
+    ServerCertVerifier, ServerCerts, TransportFactory,
+};
 use secstr::SecStr;
+use std::path::Path;
+use std::time::Duration;
 
 /// A builder for `ConnectParams`.
 ///
@@ -43,14 +50,37 @@ use secstr::SecStr;
 pub struct ConnectParamsBuilder {
     hostname: Option<String>,
     port: Option<u16>,
+    failover_hosts: Vec<(String, u16)>,
     dbuser: Option<String>,
     #[serde(skip)]
     password: Option<SecStr>,
     clientlocale: Option<String>,
     server_certs: Vec<ServerCerts>,
+    client_certs: Option<ClientCerts>,
+    tls_host_name: Option<String>,
+    public_key_pins: Vec<String>,
+    server_cert_verifier: Option<AmServerCertVerifier>,
+    compression: bool,
+    connect_timeout: Option<Duration>,
+    read_timeout: Option<Duration>,
+    write_timeout: Option<Duration>,
+    tcp_keepalive_idle: Option<Duration>,
+    tcp_keepalive_interval: Option<Duration>,
+    tcp_keepalive_count: Option<u32>,
+    heartbeat_interval: Option<Duration>,
+    reconnect_wait_limit: Option<Duration>,
+    transport_factory: Option<AmTransportFactory>,
+    proxy_type: Option<ProxyType>,
+    proxy_host: Option<String>,
+    proxy_port: Option<u16>,
+    proxy_user: Option<String>,
+    #[serde(skip)]
+    proxy_password: Option<SecStr>,
+    statement_routing: bool,
+    read_only_routing: bool,
+    database_name: Option<String>,
+    schema: Option<String>,
     options: Vec<(String, String)>,
-    #[cfg(feature = "alpha_nonblocking")]
-    use_nonblocking: bool,
 }
 
 impl ConnectParamsBuilder {
@@ -59,16 +89,79 @@ impl ConnectParamsBuilder {
         Self {
             hostname: None,
             port: None,
+            failover_hosts: Vec::new(),
             dbuser: None,
             password: None,
             clientlocale: None,
             server_certs: Vec::<ServerCerts>::default(),
+            client_certs: None,
+            tls_host_name: None,
+            public_key_pins: Vec::new(),
+            server_cert_verifier: None,
+            compression: false,
+            connect_timeout: None,
+            read_timeout: None,
+            write_timeout: None,
+            tcp_keepalive_idle: None,
+            tcp_keepalive_interval: None,
+            tcp_keepalive_count: None,
+            heartbeat_interval: None,
+            reconnect_wait_limit: None,
+            transport_factory: None,
+            proxy_type: None,
+            proxy_host: None,
+            proxy_port: None,
+            proxy_user: None,
+            proxy_password: None,
+            statement_routing: false,
+            read_only_routing: false,
+            database_name: None,
+            schema: None,
             options: vec![],
-            #[cfg(feature = "alpha_nonblocking")]
-            use_nonblocking: false,
         }
     }
 
+    /// Creates a builder from the binding of an SAP HANA service instance in the environment
+    /// variable `VCAP_SERVICES`, as it is provided to applications on Cloud Foundry (SAP BTP).
+    ///
+    /// The first binding of a service with label `hana` or `hana-cloud`, or with tag `hana`,
+    /// is used. Host, port, user, password, and (if available) the schema and the server
+    /// certificate (as [`ServerCerts::Direct`](enum.ServerCerts.html#variant.Direct))
+    /// are taken from its credentials.
+    /// Without a certificate, TLS with the public root certificates
+    /// ([`ServerCerts::RootCertificates`](enum.ServerCerts.html#variant.RootCertificates),
+    /// or [`ServerCerts::SystemStore`](enum.ServerCerts.html#variant.SystemStore) if `rustls`
+    /// is not used) is configured for `hana-cloud` bindings and for credentials with
+    /// `"encrypt": true`.
+    ///
+    /// # Errors
+    ///
+    /// `HdbError::ConnParams` if `VCAP_SERVICES` is not set, contains no suitable binding,
+    /// or the binding misses one of the mandatory values.
+    pub fn from_vcap_services() -> HdbResult<Self> {
+        super::service_binding::from_vcap_services()
+    }
+
+    /// Creates a builder from the binding of an SAP HANA service instance in the given
+    /// directory, as it is mounted into Kubernetes pods according to the
+    /// [servicebinding.io](https://servicebinding.io) specification.
+    ///
+    /// The directory can be the binding itself, or contain the bindings as subdirectories
+    /// (like `$SERVICE_BINDING_ROOT`); the first one with type (or label) `hana` or
+    /// `hana-cloud` is used. Host, port, user (or username), password, and (if available)
+    /// the schema and the server certificate
+    /// (as [`ServerCerts::Direct`](enum.ServerCerts.html#variant.Direct))
+    /// are read from the files of the binding; TLS is configured like with
+    /// [`from_vcap_services`](#method.from_vcap_services).
+    ///
+    /// # Errors
+    ///
+    /// `HdbError::ConnParams` if the directory contains no suitable binding,
+    /// or the binding misses one of the mandatory values.
+    pub fn from_service_binding_dir<P: AsRef<Path>>(path: P) -> HdbResult<Self> {
+        super::service_binding::from_service_binding_dir(path.as_ref())
+    }
+
     /// Sets the hostname.
     pub fn hostname<H: AsRef<str>>(&mut self, hostname: H) -> &mut Self {
         self.hostname = Some(hostname.as_ref().to_owned());
@@ -81,6 +174,21 @@ impl ConnectParamsBuilder {
         self
     }
 
+    /// Adds a host that is tried if the database cannot be reached on the
+    /// host that is specified with [`hostname`](#method.hostname) and [`port`](#method.port).
+    ///
+    /// Can be called repeatedly. The hosts are tried in the order in which they were added,
+    /// both when a connection is opened and when it is re-established after
+    /// the current host has become unreachable, e.g. after a takeover in a
+    /// system replication setup.
+    /// [`Connection::connect_string`](struct.Connection.html#method.connect_string)
+    /// shows the host that is currently used first.
+    pub fn failover_host<H: AsRef<str>>(&mut self, hostname: H, port: u16) -> &mut Self {
+        self.failover_hosts
+            .push((hostname.as_ref().to_owned(), port));
+        self
+    }
+
     /// Sets the database user.
     pub fn dbuser<D: AsRef<str>>(&mut self, dbuser: D) -> &mut Self {
         self.dbuser = Some(dbuser.as_ref().to_owned());
@@ -115,13 +223,6 @@ impl ConnectParamsBuilder {
         self
     }
 
-    /// Sets the client locale.
-    #[cfg(feature = "alpha_nonblocking")]
-    pub fn use_nonblocking(&mut self) -> &mut Self {
-        self.use_nonblocking = true;
-        self
-    }
-
     /// Makes the driver use TLS for the connection to the database.
     ///
     /// Requires that the server's certificate is provided with one of the
@@ -144,6 +245,352 @@ impl ConnectParamsBuilder {
         self
     }
 
+    /// Lets the client authenticate itself at the TLS layer (mutual TLS),
+    /// with the certificate chain and the private key that are provided with one of the
+    /// enum variants of [`ClientCerts`](enum.ClientCerts.html).
+    ///
+    /// Requires that TLS is used, see [`tls_with`](#method.tls_with).
+    ///
+    /// Example:
+    ///
+    /// ```rust,no_run
+    /// # use hdbconnect::{ClientCerts, ConnectParams, ServerCerts};
+    /// let mut conn_params = ConnectParams::builder()
+    ///    // ...more settings required...
+    ///    .tls_with(ServerCerts::RootCertificates)
+    ///    .tls_client_certs(ClientCerts::Files {
+    ///        cert_chain_file: "client_chain.pem".to_string(),
+    ///        private_key_file: "client_key.pem".to_string(),
+    ///    })
+    ///    .build();
+    /// ```
+    pub fn tls_client_certs(&mut self, client_certs: ClientCerts) -> &mut Self {
+        self.client_certs = Some(client_certs);
+        self
+    }
+
+    /// Makes the driver use TLS with a PKCS#12 keystore (.p12, .pfx), as it is used e.g.
+    /// by JDBC applications, both for the trust roots and for the client identity.
+    ///
+    /// This is a shortcut for
+    /// [`tls_with(ServerCerts::Pkcs12{..})`](#method.tls_with) and
+    /// [`tls_client_certs(ClientCerts::Pkcs12{..})`](#method.tls_client_certs)
+    /// with the same file and password.
+    pub fn tls_with_pkcs12<F: AsRef<str>, P: AsRef<str>>(
+        &mut self,
+        file: F,
+        password: P,
+    ) -> &mut Self {
+        let password = SecStr::from(password.as_ref().as_bytes());
+        self.tls_with(ServerCerts::Pkcs12 {
+            file: file.as_ref().to_owned(),
+            password: password.clone(),
+        });
+        self.tls_client_certs(ClientCerts::Pkcs12 {
+            file: file.as_ref().to_owned(),
+            password,
+        })
+    }
+
+    /// Sets the host name that the server certificate is validated against
+    /// (like `sslHostNameInCertificate` in JDBC).
+    ///
+    /// By default, the certificate is validated against the host to which the driver
+    /// connects. If the connection goes through a load balancer, or the host is given as
+    /// an IP address, the certificate may have been issued for another name.
+    ///
+    /// The host is still sent as SNI (server name indication), so that servers and load
+    /// balancers that route by SNI are not affected; IP addresses are not sent as SNI.
+    /// The host name applies only to the configured hosts, not to the hosts of
+    /// [statement routing](#method.statement_routing) or
+    /// [read-only routing](#method.read_only_routing), whose certificates are validated
+    /// against their own names.
+    /// With the feature `native-tls`, a differing host name can be used
+    /// only if the host is given as IP address.
+    pub fn tls_host_name_in_certificate<H: AsRef<str>>(&mut self, host_name: H) -> &mut Self {
+        self.tls_host_name = Some(host_name.as_ref().to_owned());
+        self
+    }
+
+    /// Pins a public key that the server must present during the TLS handshake.
+    ///
+    /// The pin is the hex encoded SHA-256 digest of the DER encoded `SubjectPublicKeyInfo`;
+    /// colons between the bytes are allowed. It can be computed e.g. with
+    ///
+    /// ```text
+    /// openssl x509 -in server.pem -pubkey -noout \
+    ///     | openssl pkey -pubin -outform der | openssl dgst -sha256
+    /// ```
+    ///
+    /// The pins are checked against the server's own certificate (the first one of the chain
+    /// that the server presents) only; if you call this function multiple times, one of
+    /// the pinned keys must match.
+    /// The pins are checked in addition to the validation against the
+    /// [`ServerCerts`](enum.ServerCerts.html); with
+    /// [`ServerCerts::None`](enum.ServerCerts.html#variant.None), only the pins are checked.
+    pub fn tls_public_key_pin<P: AsRef<str>>(&mut self, sha256_pin: P) -> &mut Self {
+        self.public_key_pins.push(sha256_pin.as_ref().to_owned());
+        self
+    }
+
+    /// Lets the given [`ServerCertVerifier`](trait.ServerCertVerifier.html) verify the
+    /// server's certificate chain, rather than the driver's standard verification
+    /// against the [`ServerCerts`](enum.ServerCerts.html).
+    ///
+    /// The verifier implies the use of TLS; [public key pins](#method.tls_public_key_pin)
+    /// are checked in addition.
+    /// The verifier is not part of the url representation of the builder.
+    ///
+    /// With the feature `native-tls`, which does not expose the server's certificate chain,
+    /// connections cannot use a verifier and fail to connect.
+    pub fn tls_server_cert_verifier<V: ServerCertVerifier + 'static>(
+        &mut self,
+        verifier: V,
+    ) -> &mut Self {
+        self.server_cert_verifier = Some(AmServerCertVerifier::new(verifier));
+        self
+    }
+
+    /// Lets the driver request network compression from the server.
+    ///
+    /// If the server agrees, bigger requests and replies are transferred in compressed form,
+    /// which can speed up the transfer of big resultsets considerably
+    /// when the network bandwidth is the bottleneck.
+    /// Small messages are always sent uncompressed.
+    /// If the server does not support compression, the connection works without it.
+    pub fn compression(&mut self, compression: bool) -> &mut Self {
+        self.compression = compression;
+        self
+    }
+
+    /// Sets the timeout for establishing the tcp connection to the database.
+    ///
+    /// If the timeout expires, the connection attempt fails with `HdbError::Timeout`.
+    /// By default, the operating system's timeout applies.
+    pub fn connect_timeout(&mut self, timeout: Duration) -> &mut Self {
+        self.connect_timeout = Some(timeout);
+        self
+    }
+
+    /// Sets the timeout for reading from the tcp connection.
+    ///
+    /// Note that this timeout also limits how long the driver waits for the
+    /// database to answer a request, so it must not be shorter than the longest
+    /// expected statement execution time.
+    ///
+    /// If the timeout expires, the call fails with `HdbError::Timeout`,
+    /// and the connection should not be used anymore.
+    /// By default, reads wait forever.
+    pub fn read_timeout(&mut self, timeout: Duration) -> &mut Self {
+        self.read_timeout = Some(timeout);
+        self
+    }
+
+    /// Sets the timeout for writing to the tcp connection.
+    ///
+    /// If the timeout expires, the call fails with `HdbError::Timeout`,
+    /// and the connection should not be used anymore.
+    /// By default, writes wait forever.
+    pub fn write_timeout(&mut self, timeout: Duration) -> &mut Self {
+        self.write_timeout = Some(timeout);
+        self
+    }
+
+    /// Enables TCP keepalive on the tcp connection, and sets the time the connection
+    /// must be idle before the operating system starts sending keepalive probes.
+    ///
+    /// TCP keepalive prevents firewalls and NAT gateways from silently dropping
+    /// connections that are not used for a while, and lets the operating system detect
+    /// broken connections.
+    /// By default, TCP keepalive is not enabled.
+    pub fn tcp_keepalive(&mut self, idle: Duration) -> &mut Self {
+        self.tcp_keepalive_idle = Some(idle);
+        self
+    }
+
+    /// Enables TCP keepalive and sets the time between two keepalive probes.
+    ///
+    /// By default, the operating system's setting applies.
+    pub fn tcp_keepalive_interval(&mut self, interval: Duration) -> &mut Self {
+        self.tcp_keepalive_interval = Some(interval);
+        self
+    }
+
+    /// Enables TCP keepalive and sets the number of unanswered keepalive probes
+    /// after which the operating system considers the connection broken.
+    ///
+    /// By default, the operating system's setting applies.
+    /// Not all platforms allow changing this value; where it is not supported,
+    /// it is ignored.
+    pub fn tcp_keepalive_count(&mut self, count: u32) -> &mut Self {
+        self.tcp_keepalive_count = Some(count);
+        self
+    }
+
+    /// Lets the driver send a ping request, which executes no SQL, to the database
+    /// whenever the connection was not used for the given interval.
+    ///
+    /// The heartbeat keeps the session alive also where network components drop
+    /// idle connections on a higher level than TCP, and it detects broken connections
+    /// before the application uses them: a connection that was reset is re-established.
+    ///
+    /// The heartbeat applies to [`Connection`](struct.Connection.html)s,
+    /// and thus also to the connections in a pool that is managed by a
+    /// [`ConnectionManager`](struct.ConnectionManager.html).
+    /// An `AsyncConnection` is pinged only if it is created within a tokio runtime.
+    /// The additional connections that the driver opens for statement routing,
+    /// read-only routing, or for cancelling a statement, are not pinged.
+    /// Connections that are busy, or have a transaction with uncommitted changes, are not
+    /// pinged. If the session nevertheless had to be re-established by the heartbeat while
+    /// a transaction was open, the next call of the application returns
+    /// `HdbError::TransactionLost`.
+    /// By default, no heartbeat is sent.
+    pub fn heartbeat_interval(&mut self, interval: Duration) -> &mut Self {
+        self.heartbeat_interval = Some(interval);
+        self
+    }
+
+    /// Limits how long the driver repeats its attempts to re-establish a lost connection.
+    ///
+    /// While the server is not reachable, e.g. during a takeover in a system replication
+    /// setup, the driver repeats the attempt to reconnect every second, within the
+    /// reconnect wait timeout that the server announced, but not longer than this limit.
+    /// The connection cannot be used by other threads meanwhile.
+    /// A zero duration lets the driver try only once.
+    /// By default, the limit is 5 seconds.
+    pub fn reconnect_wait_limit(&mut self, limit: Duration) -> &mut Self {
+        self.reconnect_wait_limit = Some(limit);
+        self
+    }
+
+    /// Lets the driver communicate with the database over the streams that are opened by the
+    /// given [`TransportFactory`](trait.TransportFactory.html), rather than over
+    /// tcp connections to hostname and port.
+    ///
+    /// Hostname and port are still required; they are handed over to the factory
+    /// within the `ConnectParams`, and the hostname is used for TLS.
+    ///
+    /// The factory is not part of the url representation of the builder.
+    /// It can only be used with [`Connection`](struct.Connection.html)
+    /// (and thus with [`ConnectionManager`](struct.ConnectionManager.html)),
+    /// not with `AsyncConnection`.
+    pub fn transport_factory<F: TransportFactory + 'static>(&mut self, factory: F) -> &mut Self {
+        self.transport_factory = Some(AmTransportFactory::new(factory));
+        self
+    }
+
+    /// Sets the type of the proxy through which the connection is established.
+    ///
+    /// By default, a SOCKS5 proxy is assumed if a proxy host is set.
+    pub fn proxy_type(&mut self, proxy_type: ProxyType) -> &mut Self {
+        self.proxy_type = Some(proxy_type);
+        self
+    }
+
+    /// Lets the driver connect to the database through a proxy on the given host.
+    ///
+    /// The driver connects to the proxy, lets the proxy connect to the database,
+    /// and only then starts the communication with the database (and TLS, if configured).
+    /// Requires also a [`proxy_port`](#method.proxy_port).
+    /// The proxy is not used if a [`transport_factory`](#method.transport_factory) is set.
+    pub fn proxy_host<H: AsRef<str>>(&mut self, proxy_host: H) -> &mut Self {
+        self.proxy_host = Some(proxy_host.as_ref().to_owned());
+        self
+    }
+
+    /// Sets the port of the proxy.
+    pub fn proxy_port(&mut self, proxy_port: u16) -> &mut Self {
+        self.proxy_port = Some(proxy_port);
+        self
+    }
+
+    /// Sets the user for authenticating at the proxy.
+    pub fn proxy_user<U: AsRef<str>>(&mut self, proxy_user: U) -> &mut Self {
+        self.proxy_user = Some(proxy_user.as_ref().to_owned());
+        self
+    }
+
+    /// Sets the password for authenticating at the proxy.
+    pub fn proxy_password<P: AsRef<str>>(&mut self, proxy_password: P) -> &mut Self {
+        self.proxy_password = Some(SecStr::new(proxy_password.as_ref().as_bytes().to_vec()));
+        self
+    }
+
+    /// Lets the driver route prepared statements in scale-out systems to the host
+    /// that owns the affected table, rather than having the server forward them.
+    ///
+    /// When a statement is prepared, the server reports where the data of the
+    /// affected table is located. If the table is not located on the host of the
+    /// connection, the driver opens a secondary connection to the owning host
+    /// (or reuses an existing one), with the same credentials and settings,
+    /// and prepares and executes the statement there.
+    /// If the secondary connection cannot be opened, the statement is executed
+    /// on the primary connection.
+    /// The table location of a routed statement is remembered per connection, so that
+    /// preparing the same statement again goes to the secondary connection directly.
+    ///
+    /// Each secondary connection has its own database session, and thus its own transactions.
+    /// Statements are therefore only routed while auto-commit is switched on; with
+    /// auto-commit switched off, all statements are executed on the primary connection,
+    /// also those that were prepared and routed before, so that a transaction never spans
+    /// several sessions.
+    /// Routing is done per table, not per partition: statements on tables whose partitions
+    /// are spread over several hosts are routed to the first of these hosts, which forwards
+    /// them to the owning partition where needed.
+    ///
+    /// Statement routing applies to [`Connection`](struct.Connection.html)s.
+    /// By default, it is not active.
+    pub fn statement_routing(&mut self, statement_routing: bool) -> &mut Self {
+        self.statement_routing = statement_routing;
+        self
+    }
+
+    /// Lets the driver route read-only statements to the secondary site
+    /// of an Active/Active (read enabled) system.
+    ///
+    /// The driver announces its support for Active/Active systems when it connects.
+    /// If the server accepts this, statements that are explicitly marked as read-only,
+    /// i.e., that are executed with
+    /// [`Connection::query_read_only`](struct.Connection.html#method.query_read_only)
+    /// or prepared with
+    /// [`Connection::prepare_read_only`](struct.Connection.html#method.prepare_read_only),
+    /// are executed on a companion connection to the secondary site, which the driver opens
+    /// from the topology information, with the same credentials and settings.
+    /// If the secondary site is unavailable, these statements are executed on the primary;
+    /// after a failed attempt to connect to the secondary site, the next attempt is made
+    /// 30 seconds later at the earliest.
+    ///
+    /// The secondary site may lag behind the primary site;
+    /// changes of the own, uncommitted transaction are not visible there.
+    ///
+    /// Read-only routing applies to [`Connection`](struct.Connection.html)s.
+    /// By default, it is not active.
+    pub fn read_only_routing(&mut self, read_only_routing: bool) -> &mut Self {
+        self.read_only_routing = read_only_routing;
+        self
+    }
+
+    /// Sets the name of the tenant database to connect to, in a multitenant database container.
+    ///
+    /// The [`hostname`](#method.hostname) and [`port`](#method.port) then denote the
+    /// system database; the driver asks it where the tenant database can be reached,
+    /// and connects to the tenant database's SQL port instead.
+    /// If the system database reports that it is itself the requested database,
+    /// the connection is used as it is.
+    /// When the connection is re-established, the lookup is repeated, using the configured
+    /// hosts (including the [failover hosts](#method.failover_host)) of the system database.
+    pub fn database_name<D: AsRef<str>>(&mut self, database_name: D) -> &mut Self {
+        self.database_name = Some(database_name.as_ref().to_owned());
+        self
+    }
+
+    /// Sets the schema that is to be used as current schema after logon,
+    /// instead of the user's default schema.
+    pub fn schema<S: AsRef<str>>(&mut self, schema: S) -> &mut Self {
+        self.schema = Some(schema.as_ref().to_owned());
+        self
+    }
+
     /// Adds a runtime parameter.
     pub fn option(&mut self, name: &str, value: &str) -> &mut Self {
         self.options.push((name.to_string(), value.to_string()));
@@ -156,6 +603,52 @@ impl ConnectParamsBuilder {
     /// `HdbError::Usage` if the builder was not yet configured to
     /// create a meaningful `ConnectParams`
     pub fn build(&self) -> HdbResult<ConnectParams> {
+        for timeout in [self.connect_timeout, self.read_timeout, self.write_timeout]
+            .iter()
+            .flatten()
+        {
+            if timeout.as_nanos() == 0 {
+                return Err(HdbError::Usage("timeouts must not be zero"));
+            }
+        }
+        for interval in [
+            self.tcp_keepalive_idle,
+            self.tcp_keepalive_interval,
+            self.heartbeat_interval,
+        ]
+        .iter()
+        .flatten()
+        {
+            if interval.as_secs() == 0 {
+                return Err(HdbError::Usage(
+                    "keepalive and heartbeat intervals must be at least one second",
+                ));
+            }
+        }
+
+        let use_tls = !self.server_certs.is_empty() || self.server_cert_verifier.is_some();
+        if !use_tls
+            && (self.client_certs.is_some()
+                || self.tls_host_name.is_some()
+                || !self.public_key_pins.is_empty())
+        {
+            return Err(HdbError::Usage(
+                "client certificates, the host name in the certificate, and public key pins \
+                 can only be used with TLS",
+            ));
+        }
+        let public_key_pins = self
+            .public_key_pins
+            .iter()
+            .map(|pin| {
+                parse_public_key_pin(pin).ok_or(HdbError::Usage(
+                    "public key pins must be hex encoded SHA-256 digests",
+                ))
+            })
+            .collect::<HdbResult<Vec<[u8; 32]>>>()?;
+
+        let proxy = self.build_proxy_params()?;
+
         let host = match self.hostname {
             Some(ref s) => s.clone(),
             None => return Err(HdbError::Usage("hostname is missing")),
@@ -169,6 +662,13 @@ impl ConnectParamsBuilder {
                 None => return Err(HdbError::Usage("port is missing")),
             }
         );
+        let mut hosts = vec![(host, addr)];
+        for (failover_host, failover_port) in &self.failover_hosts {
+            hosts.push((
+                failover_host.clone(),
+                format!("{}:{}", failover_host, failover_port),
+            ));
+        }
         let dbuser = match self.dbuser {
             Some(ref s) => s.clone(),
             None => return Err(HdbError::Usage("dbuser is missing")),
@@ -179,17 +679,54 @@ impl ConnectParamsBuilder {
         };
 
         Ok(ConnectParams::new(
-            host,
-            addr,
+            self,
+            hosts,
             dbuser,
             password,
-            self.clientlocale.clone(),
-            self.server_certs.clone(),
-            #[cfg(feature = "alpha_nonblocking")]
-            self.use_nonblocking,
+            proxy,
+            public_key_pins,
         ))
     }
 
+    fn build_proxy_params(&self) -> HdbResult<Option<ProxyParams>> {
+        let proxy_host = if let Some(ref proxy_host) = self.proxy_host {
+            proxy_host.clone()
+        } else if self.proxy_type.is_some()
+            || self.proxy_port.is_some()
+            || self.proxy_user.is_some()
+            || self.proxy_password.is_some()
+        {
+            return Err(HdbError::Usage("proxy_host is missing"));
+        } else {
+            return Ok(None);
+        };
+        let proxy_port = self
+            .proxy_port
+            .ok_or(HdbError::Usage("proxy_port is missing"))?;
+        if self.proxy_password.is_some() && self.proxy_user.is_none() {
+            return Err(HdbError::Usage("proxy_user is missing"));
+        }
+        Ok(Some(ProxyParams::new(
+            self.proxy_type.unwrap_or(ProxyType::Socks5),
+            proxy_host,
+            proxy_port,
+            self.proxy_user.clone(),
+            self.proxy_password.clone(),
+        )))
+    }
+
+    fn get_failover_hosts_as_string(&self) -> String {
+        self.failover_hosts
+            .iter()
+            .fold(String::new(), |mut s, (hostname, port)| {
+                s.push(',');
+                s.push_str(hostname);
+                s.push(':');
+                s.push_str(&port.to_string());
+                s
+            })
+    }
+
     /// Returns the url for this connection
     ///
     /// # Errors
@@ -201,11 +738,12 @@ impl ConnectParamsBuilder {
             if let Some(hostname) = &self.hostname {
                 if let Some(port) = &self.port {
                     return Ok(format!(
-                        "{}://{}@{}:{}{}",
+                        "{}://{}@{}:{}{}{}",
                         self.get_protocol_name(),
                         dbuser,
                         hostname,
                         port,
+                        self.get_failover_hosts_as_string(),
                         self.get_options_as_parameters()
                     ));
                 }
@@ -215,7 +753,7 @@ impl ConnectParamsBuilder {
     }
 
     fn get_protocol_name(&self) -> &str {
-        if self.server_certs.is_empty() {
+        if self.server_certs.is_empty() && self.server_cert_verifier.is_none() {
             "hdbsql"
         } else {
             "hdbsqls"
@@ -224,23 +762,91 @@ impl ConnectParamsBuilder {
 
     fn get_options_as_parameters(&self) -> String {
         let mut result = String::with_capacity(200);
-        for (index, s) in self
-            .options
-            .iter()
-            .map(|(k, v)| {
-                if v.is_empty() {
-                    k.clone()
-                } else {
-                    format!("{}={}", k, v)
-                }
-            })
-            .chain(self.server_certs.iter().map(ToString::to_string))
-            .chain(
-                self.clientlocale
-                    .iter()
-                    .map(|l| format!("{}={}", super::cp_url::OPTION_CLIENT_LOCALE, l)),
-            )
-            .enumerate()
+        for (index, s) in
+            self.options
+                .iter()
+                .map(|(k, v)| {
+                    if v.is_empty() {
+                        k.clone()
+                    } else {
+                        format!("{}={}", k, v)
+                    }
+                })
+                .chain(self.server_certs.iter().map(ToString::to_string))
+                .chain(self.client_certs.iter().map(ToString::to_string))
+                .chain(
+                    self.tls_host_name.iter().map(|name| {
+                        format!("{}={}", super::cp_url::OPTION_HOST_NAME_IN_CERT, name)
+                    }),
+                )
+                .chain(
+                    self.public_key_pins
+                        .iter()
+                        .map(|pin| format!("{}={}", super::cp_url::OPTION_PUBLIC_KEY_PIN, pin)),
+                )
+                .chain(
+                    self.clientlocale
+                        .iter()
+                        .map(|l| format!("{}={}", super::cp_url::OPTION_CLIENT_LOCALE, l)),
+                )
+                .chain(
+                    Some(super::cp_url::OPTION_COMPRESS.to_string())
+                        .into_iter()
+                        .filter(|_| self.compression),
+                )
+                .chain(super::cp_url::duration_option(
+                    super::cp_url::OPTION_CONNECT_TIMEOUT,
+                    self.connect_timeout,
+                ))
+                .chain(super::cp_url::duration_option(
+                    super::cp_url::OPTION_READ_TIMEOUT,
+                    self.read_timeout,
+                ))
+                .chain(super::cp_url::duration_option(
+                    super::cp_url::OPTION_WRITE_TIMEOUT,
+                    self.write_timeout,
+                ))
+                .chain(super::cp_url::duration_option(
+                    super::cp_url::OPTION_TCP_KEEPALIVE_IDLE,
+                    self.tcp_keepalive_idle,
+                ))
+                .chain(super::cp_url::duration_option(
+                    super::cp_url::OPTION_TCP_KEEPALIVE_INTERVAL,
+                    self.tcp_keepalive_interval,
+                ))
+                .chain(self.tcp_keepalive_count.map(|count| {
+                    format!("{}={}", super::cp_url::OPTION_TCP_KEEPALIVE_COUNT, count)
+                }))
+                .chain(super::cp_url::duration_option(
+                    super::cp_url::OPTION_HEARTBEAT_INTERVAL,
+                    self.heartbeat_interval,
+                ))
+                .chain(super::cp_url::duration_option(
+                    super::cp_url::OPTION_RECONNECT_WAIT_LIMIT,
+                    self.reconnect_wait_limit,
+                ))
+                .chain(self.get_proxy_options_as_parameters())
+                .chain(
+                    Some(super::cp_url::OPTION_STATEMENT_ROUTING.to_string())
+                        .into_iter()
+                        .filter(|_| self.statement_routing),
+                )
+                .chain(
+                    Some(super::cp_url::OPTION_READ_ONLY_ROUTING.to_string())
+                        .into_iter()
+                        .filter(|_| self.read_only_routing),
+                )
+                .chain(
+                    self.database_name
+                        .iter()
+                        .map(|name| format!("{}={}", super::cp_url::OPTION_DATABASE_NAME, name)),
+                )
+                .chain(
+                    self.schema.iter().map(|schema| {
+                        format!("{}={}", super::cp_url::OPTION_CURRENT_SCHEMA, schema)
+                    }),
+                )
+                .enumerate()
         {
             let prefix = if index == 0 { "?" } else { "&" };
             result.push_str(&format!("{}{}", prefix, s));
@@ -248,6 +854,40 @@ impl ConnectParamsBuilder {
         result
     }
 
+    // the proxy password is omitted, like the password of the dbuser
+    fn get_proxy_options_as_parameters(&self) -> Vec<String> {
+        let mut result = Vec::<String>::new();
+        if let Some(proxy_type) = self.proxy_type {
+            result.push(format!(
+                "{}={}",
+                super::cp_url::OPTION_PROXY_TYPE,
+                proxy_type
+            ));
+        }
+        if let Some(ref proxy_host) = self.proxy_host {
+            result.push(format!(
+                "{}={}",
+                super::cp_url::OPTION_PROXY_HOST,
+                proxy_host
+            ));
+        }
+        if let Some(proxy_port) = self.proxy_port {
+            result.push(format!(
+                "{}={}",
+                super::cp_url::OPTION_PROXY_PORT,
+                proxy_port
+            ));
+        }
+        if let Some(ref proxy_user) = self.proxy_user {
+            result.push(format!(
+                "{}={}",
+                super::cp_url::OPTION_PROXY_USER,
+                proxy_user
+            ));
+        }
+        result
+    }
+
     /// Getter
     pub fn get_hostname(&self) -> Option<&str> {
         self.hostname.as_deref()
@@ -268,6 +908,11 @@ impl ConnectParamsBuilder {
         self.port
     }
 
+    /// Getter
+    pub fn get_failover_hosts(&self) -> &Vec<(String, u16)> {
+        &self.failover_hosts
+    }
+
     /// Getter
     pub fn get_clientlocale(&self) -> Option<&str> {
         self.clientlocale.as_deref()
@@ -278,6 +923,129 @@ impl ConnectParamsBuilder {
         &self.server_certs
     }
 
+    /// Getter
+    pub fn get_client_certs(&self) -> Option<&ClientCerts> {
+        self.client_certs.as_ref()
+    }
+
+    /// Getter
+    pub fn get_tls_host_name_in_certificate(&self) -> Option<&str> {
+        self.tls_host_name.as_deref()
+    }
+
+    /// Getter
+    pub fn get_tls_public_key_pins(&self) -> &[String] {
+        &self.public_key_pins
+    }
+
+    /// Getter
+    pub fn has_server_cert_verifier(&self) -> bool {
+        self.server_cert_verifier.is_some()
+    }
+
+    pub(crate) fn get_server_cert_verifier(&self) -> Option<&AmServerCertVerifier> {
+        self.server_cert_verifier.as_ref()
+    }
+
+    /// Getter
+    pub fn get_compression(&self) -> bool {
+        self.compression
+    }
+
+    /// Getter
+    pub fn get_statement_routing(&self) -> bool {
+        self.statement_routing
+    }
+
+    /// Getter
+    pub fn get_read_only_routing(&self) -> bool {
+        self.read_only_routing
+    }
+
+    /// Getter
+    pub fn get_database_name(&self) -> Option<&str> {
+        self.database_name.as_deref()
+    }
+
+    /// Getter
+    pub fn get_schema(&self) -> Option<&str> {
+        self.schema.as_deref()
+    }
+
+    /// Getter
+    pub fn get_connect_timeout(&self) -> Option<Duration> {
+        self.connect_timeout
+    }
+
+    /// Getter
+    pub fn get_read_timeout(&self) -> Option<Duration> {
+        self.read_timeout
+    }
+
+    /// Getter
+    pub fn get_write_timeout(&self) -> Option<Duration> {
+        self.write_timeout
+    }
+
+    /// Getter
+    pub fn get_tcp_keepalive(&self) -> Option<Duration> {
+        self.tcp_keepalive_idle
+    }
+
+    /// Getter
+    pub fn get_tcp_keepalive_interval(&self) -> Option<Duration> {
+        self.tcp_keepalive_interval
+    }
+
+    /// Getter
+    pub fn get_tcp_keepalive_count(&self) -> Option<u32> {
+        self.tcp_keepalive_count
+    }
+
+    /// Getter
+    pub fn get_heartbeat_interval(&self) -> Option<Duration> {
+        self.heartbeat_interval
+    }
+
+    /// Getter
+    pub fn get_reconnect_wait_limit(&self) -> Option<Duration> {
+        self.reconnect_wait_limit
+    }
+
+    /// Getter
+    pub fn has_transport_factory(&self) -> bool {
+        self.transport_factory.is_some()
+    }
+
+    pub(crate) fn get_transport_factory(&self) -> Option<&AmTransportFactory> {
+        self.transport_factory.as_ref()
+    }
+
+    /// Getter
+    pub fn get_proxy_type(&self) -> Option<ProxyType> {
+        self.proxy_type
+    }
+
+    /// Getter
+    pub fn get_proxy_host(&self) -> Option<&str> {
+        self.proxy_host.as_deref()
+    }
+
+    /// Getter
+    pub fn get_proxy_port(&self) -> Option<u16> {
+        self.proxy_port
+    }
+
+    /// Getter
+    pub fn get_proxy_user(&self) -> Option<&str> {
+        self.proxy_user.as_deref()
+    }
+
+    /// Getter
+    pub fn get_proxy_password(&self) -> Option<&SecStr> {
+        self.proxy_password.as_ref()
+    }
+
     /// Getter
     pub fn get_options(&self) -> &Vec<(String, String)> {
         &self.options
@@ -392,11 +1160,12 @@ mod test {
             .password("schLau")
             .clientlocale("de_DE")
             .tls_with(crate::ServerCerts::Directory("TCD".to_string()))
-            .tls_with(crate::ServerCerts::RootCertificates);
+            .tls_with(crate::ServerCerts::RootCertificates)
+            .compression(true);
 
         let serialized = serde_json::to_string(&data).unwrap();
         assert_eq!(
-            r#"{"x":"hdbsqls://MEIER@abcd123:2222?tls_certificate_dir=TCD&use_mozillas_root_certificates&client_locale=de_DE"}"#,
+            r#"{"x":"hdbsqls://MEIER@abcd123:2222?tls_certificate_dir=TCD&use_mozillas_root_certificates&client_locale=de_DE&compress"}"#,
             serialized
         );
 
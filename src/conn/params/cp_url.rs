@@ -1,10 +1,94 @@
+use crate::{HdbError, HdbResult, ProxyType};
+use std::time::Duration;
+
 pub const OPTION_CERT_DIR: &str = "tls_certificate_dir";
 pub const OPTION_CERT_ENV: &str = "tls_certificate_env";
 pub const OPTION_CERT_DIRECT: &str = "tls_certificate_direct";
 pub const OPTION_CERT_MOZILLA: &str = "use_mozillas_root_certificates";
+pub const OPTION_CERT_SYSTEM: &str = "use_system_root_certificates";
+pub const OPTION_TRUST_STORE: &str = "tls_trust_store";
+pub const OPTION_TRUST_STORE_PASSWORD: &str = "tls_trust_store_password";
 pub const OPTION_INSECURE_NO_CHECK: &str = "insecure_omit_server_certificate_check";
+pub const OPTION_CLIENT_CERT_FILE: &str = "tls_client_certificate_file";
+pub const OPTION_CLIENT_KEY_FILE: &str = "tls_client_key_file";
+pub const OPTION_CLIENT_CERT_ENV: &str = "tls_client_certificate_env";
+pub const OPTION_CLIENT_CERT_DIRECT: &str = "tls_client_certificate_direct";
+pub const OPTION_KEY_STORE: &str = "tls_key_store";
+pub const OPTION_KEY_STORE_PASSWORD: &str = "tls_key_store_password";
+pub const OPTION_HOST_NAME_IN_CERT: &str = "tls_host_name_in_certificate";
+pub const OPTION_PUBLIC_KEY_PIN: &str = "tls_public_key_pin";
 
 pub const OPTION_CLIENT_LOCALE: &str = "client_locale";
 pub const OPTION_CLIENT_LOCALE_FROM_ENV: &str = "client_locale_from_env";
 
-pub const OPTION_NONBLOCKING: &str = "nonblocking";
+pub const OPTION_COMPRESS: &str = "compress";
+
+pub const OPTION_CONNECT_TIMEOUT: &str = "connect_timeout";
+pub const OPTION_READ_TIMEOUT: &str = "read_timeout";
+pub const OPTION_WRITE_TIMEOUT: &str = "write_timeout";
+
+pub const OPTION_TCP_KEEPALIVE_IDLE: &str = "tcp_keepalive_idle";
+pub const OPTION_TCP_KEEPALIVE_INTERVAL: &str = "tcp_keepalive_interval";
+pub const OPTION_TCP_KEEPALIVE_COUNT: &str = "tcp_keepalive_count";
+pub const OPTION_HEARTBEAT_INTERVAL: &str = "heartbeat_interval";
+pub const OPTION_RECONNECT_WAIT_LIMIT: &str = "reconnect_wait_limit";
+
+pub const OPTION_PROXY_TYPE: &str = "proxy_type";
+pub const OPTION_PROXY_HOST: &str = "proxy_host";
+pub const OPTION_PROXY_PORT: &str = "proxy_port";
+pub const OPTION_PROXY_USER: &str = "proxy_user";
+pub const OPTION_PROXY_PASSWORD: &str = "proxy_password";
+
+pub const OPTION_STATEMENT_ROUTING: &str = "statement_routing";
+pub const OPTION_READ_ONLY_ROUTING: &str = "read_only_routing";
+
+pub const OPTION_DATABASE_NAME: &str = "database_name";
+pub const OPTION_CURRENT_SCHEMA: &str = "current_schema";
+
+// Renders a duration as url option, with the value in milliseconds.
+pub fn duration_option(name: &str, o_duration: Option<Duration>) -> Option<String> {
+    o_duration.map(|duration| format!("{}={}", name, duration.as_millis()))
+}
+
+// Parses the value of a url option that specifies a duration in milliseconds.
+pub fn parse_duration(name: &str, value: &str) -> HdbResult<Duration> {
+    value
+        .parse::<u64>()
+        .map(Duration::from_millis)
+        .map_err(|_| {
+            HdbError::UsageDetailed(format!(
+                "url option {} requires a number of milliseconds as value, got {:?}",
+                name, value
+            ))
+        })
+}
+
+// Parses the value of a url option that specifies a count.
+pub fn parse_count(name: &str, value: &str) -> HdbResult<u32> {
+    value.parse::<u32>().map_err(|_| {
+        HdbError::UsageDetailed(format!(
+            "url option {} requires a non-negative number as value, got {:?}",
+            name, value
+        ))
+    })
+}
+
+// Parses the value of a url option that specifies a port.
+pub fn parse_port(name: &str, value: &str) -> HdbResult<u16> {
+    value.parse::<u16>().map_err(|_| {
+        HdbError::UsageDetailed(format!(
+            "url option {} requires a port number as value, got {:?}",
+            name, value
+        ))
+    })
+}
+
+// Parses the value of a url option that specifies the type of a proxy.
+pub fn parse_proxy_type(name: &str, value: &str) -> HdbResult<ProxyType> {
+    ProxyType::from_url_value(value).ok_or_else(|| {
+        HdbError::UsageDetailed(format!(
+            "url option {} requires 'socks5' or 'http' as value, got {:?}",
+            name, value
+        ))
+    })
+}
@@ -1,4 +1,4 @@
-use crate::{ConnectParams, ConnectParamsBuilder, HdbError, HdbResult};
+use crate::{ConnectParams, ConnectParamsBuilder, HdbResult};
 use url::Url;
 
 /// A trait implemented by types that can be converted into a `ConnectParams`.
@@ -37,9 +37,8 @@ impl IntoConnectParams for &ConnectParamsBuilder {
 
 impl<'a> IntoConnectParams for &'a str {
     fn into_connect_params(self) -> HdbResult<ConnectParams> {
-        Url::parse(self)
-            .map_err(|e| HdbError::conn_params(Box::new(e)))?
-            .into_connect_params()
+        // goes via the builder, which can handle a list of hosts
+        crate::IntoConnectParamsBuilder::into_connect_params_builder(self)?.build()
     }
 }
 
@@ -1,7 +1,8 @@
-use super::connect_params::ServerCerts;
+use super::connect_params::{ClientCerts, ServerCerts};
 use super::connect_params_builder::ConnectParamsBuilder;
 use super::cp_url;
 use crate::{HdbError, HdbResult};
+use secstr::SecStr;
 use url::Url;
 
 /// A trait implemented by types that can be converted into a `ConnectParamsBuilder`.
@@ -32,12 +33,48 @@ impl IntoConnectParamsBuilder for ConnectParamsBuilder {
 
 impl<'a> IntoConnectParamsBuilder for &'a str {
     fn into_connect_params_builder(self) -> HdbResult<ConnectParamsBuilder> {
-        Url::parse(self)
+        let (url, failover_hosts) = split_off_failover_hosts(self)?;
+        let mut builder = Url::parse(&url)
             .map_err(|e| HdbError::conn_params(Box::new(e)))?
-            .into_connect_params_builder()
+            .into_connect_params_builder()?;
+        for (host, port) in failover_hosts {
+            builder.failover_host(host, port);
+        }
+        Ok(builder)
     }
 }
 
+// Url cannot parse a list of hosts, so we take out all but the first host before parsing.
+fn split_off_failover_hosts(s: &str) -> HdbResult<(String, Vec<(&str, u16)>)> {
+    let authority_start = match s.find("://") {
+        Some(pos) => pos + 3,
+        None => return Ok((s.to_string(), Vec::new())),
+    };
+    let rest = &s[authority_start..];
+    let authority_len = rest.find(['/', '?']).unwrap_or(rest.len());
+    let hosts_start = rest[..authority_len].rfind('@').map_or(0, |pos| pos + 1);
+    let mut hosts = rest[hosts_start..authority_len].split(',');
+    let first_host = hosts.next().unwrap_or_default();
+    let failover_hosts = hosts
+        .map(|host_and_port| {
+            let pos = host_and_port.rfind(':').ok_or_else(|| {
+                HdbError::UsageDetailed(format!("host {:?} has no port", host_and_port))
+            })?;
+            let port = host_and_port[pos + 1..].parse::<u16>().map_err(|_| {
+                HdbError::UsageDetailed(format!("host {:?} has no valid port", host_and_port))
+            })?;
+            Ok((&host_and_port[..pos], port))
+        })
+        .collect::<HdbResult<Vec<(&str, u16)>>>()?;
+    let url = format!(
+        "{}{}{}",
+        &s[..authority_start + hosts_start],
+        first_host,
+        &rest[authority_len..]
+    );
+    Ok((url, failover_hosts))
+}
+
 impl IntoConnectParamsBuilder for String {
     fn into_connect_params_builder(self) -> HdbResult<ConnectParamsBuilder> {
         self.as_str().into_connect_params_builder()
@@ -45,6 +82,7 @@ impl IntoConnectParamsBuilder for String {
 }
 
 impl IntoConnectParamsBuilder for Url {
+    #[allow(clippy::too_many_lines)]
     fn into_connect_params_builder(self) -> HdbResult<ConnectParamsBuilder> {
         let mut builder = ConnectParamsBuilder::new();
         self.host_str().as_ref().map(|host| builder.hostname(host));
@@ -67,6 +105,12 @@ impl IntoConnectParamsBuilder for Url {
         };
 
         let mut server_certs = Vec::<ServerCerts>::new();
+        let mut o_client_cert_file = None;
+        let mut o_client_key_file = None;
+        let mut o_trust_store = None;
+        let mut trust_store_password = SecStr::new(Vec::new());
+        let mut o_key_store = None;
+        let mut key_store_password = SecStr::new(Vec::new());
 
         for (name, value) in self.query_pairs() {
             match name.as_ref() {
@@ -87,33 +131,136 @@ impl IntoConnectParamsBuilder for Url {
                 cp_url::OPTION_CERT_MOZILLA => {
                     server_certs.push(ServerCerts::RootCertificates);
                 }
+                cp_url::OPTION_CERT_SYSTEM => {
+                    server_certs.push(ServerCerts::SystemStore);
+                }
+                cp_url::OPTION_TRUST_STORE => {
+                    o_trust_store = Some(value.to_string());
+                }
+                cp_url::OPTION_TRUST_STORE_PASSWORD => {
+                    trust_store_password = SecStr::from(value.as_bytes());
+                }
+                cp_url::OPTION_KEY_STORE => {
+                    o_key_store = Some(value.to_string());
+                }
+                cp_url::OPTION_KEY_STORE_PASSWORD => {
+                    key_store_password = SecStr::from(value.as_bytes());
+                }
                 cp_url::OPTION_INSECURE_NO_CHECK => {
                     server_certs.push(ServerCerts::None);
                 }
-                cp_url::OPTION_NONBLOCKING => {
-                    #[cfg(feature = "alpha_nonblocking")]
-                    builder.use_nonblocking();
-
-                    #[cfg(not(feature = "alpha_nonblocking"))]
-                    return Err(HdbError::UsageDetailed(format!(
-                        "url option {} requires feature alpha_nonblocking",
-                        cp_url::OPTION_NONBLOCKING
-                    )));
+                cp_url::OPTION_CLIENT_CERT_FILE => {
+                    o_client_cert_file = Some(value.to_string());
+                }
+                cp_url::OPTION_CLIENT_KEY_FILE => {
+                    o_client_key_file = Some(value.to_string());
+                }
+                cp_url::OPTION_HOST_NAME_IN_CERT => {
+                    builder.tls_host_name_in_certificate(value);
+                }
+                cp_url::OPTION_PUBLIC_KEY_PIN => {
+                    builder.tls_public_key_pin(value);
+                }
+                cp_url::OPTION_CLIENT_CERT_ENV => {
+                    builder.tls_client_certs(ClientCerts::Environment(value.to_string()));
+                }
+                cp_url::OPTION_COMPRESS => {
+                    builder.compression(true);
+                }
+                cp_url::OPTION_STATEMENT_ROUTING => {
+                    builder.statement_routing(true);
+                }
+                cp_url::OPTION_READ_ONLY_ROUTING => {
+                    builder.read_only_routing(true);
+                }
+                cp_url::OPTION_DATABASE_NAME => {
+                    builder.database_name(value);
+                }
+                cp_url::OPTION_CURRENT_SCHEMA => {
+                    builder.schema(value);
+                }
+                cp_url::OPTION_CONNECT_TIMEOUT => {
+                    builder.connect_timeout(cp_url::parse_duration(&name, &value)?);
+                }
+                cp_url::OPTION_READ_TIMEOUT => {
+                    builder.read_timeout(cp_url::parse_duration(&name, &value)?);
+                }
+                cp_url::OPTION_WRITE_TIMEOUT => {
+                    builder.write_timeout(cp_url::parse_duration(&name, &value)?);
+                }
+                cp_url::OPTION_TCP_KEEPALIVE_IDLE => {
+                    builder.tcp_keepalive(cp_url::parse_duration(&name, &value)?);
+                }
+                cp_url::OPTION_TCP_KEEPALIVE_INTERVAL => {
+                    builder.tcp_keepalive_interval(cp_url::parse_duration(&name, &value)?);
+                }
+                cp_url::OPTION_TCP_KEEPALIVE_COUNT => {
+                    builder.tcp_keepalive_count(cp_url::parse_count(&name, &value)?);
+                }
+                cp_url::OPTION_HEARTBEAT_INTERVAL => {
+                    builder.heartbeat_interval(cp_url::parse_duration(&name, &value)?);
+                }
+                cp_url::OPTION_RECONNECT_WAIT_LIMIT => {
+                    builder.reconnect_wait_limit(cp_url::parse_duration(&name, &value)?);
+                }
+                cp_url::OPTION_PROXY_TYPE => {
+                    builder.proxy_type(cp_url::parse_proxy_type(&name, &value)?);
+                }
+                cp_url::OPTION_PROXY_HOST => {
+                    builder.proxy_host(value);
+                }
+                cp_url::OPTION_PROXY_PORT => {
+                    builder.proxy_port(cp_url::parse_port(&name, &value)?);
+                }
+                cp_url::OPTION_PROXY_USER => {
+                    builder.proxy_user(value);
+                }
+                cp_url::OPTION_PROXY_PASSWORD => {
+                    builder.proxy_password(value);
                 }
                 _ => log::warn!("option {} not supported", name),
             }
         }
 
+        if let Some(file) = o_trust_store {
+            server_certs.push(ServerCerts::Pkcs12 {
+                file,
+                password: trust_store_password,
+            });
+        }
+        if let Some(file) = o_key_store {
+            builder.tls_client_certs(ClientCerts::Pkcs12 {
+                file,
+                password: key_store_password,
+            });
+        }
+
         if use_tls && server_certs.is_empty() {
             return Err(HdbError::Usage(
                 "Using 'hdbsqls' requires one of the url-options 'tls_certificate_dir', \
                 'tls_certificate_env', 'tls_certificate_direct', \
-                'use_mozillas_root_certificates', or 'insecure_omit_server_certificate_check'",
+                'use_mozillas_root_certificates', 'use_system_root_certificates', \
+                'tls_trust_store', or 'insecure_omit_server_certificate_check'",
             ));
         }
         for cert in server_certs {
             builder.tls_with(cert);
         }
+        match (o_client_cert_file, o_client_key_file) {
+            (Some(cert_chain_file), Some(private_key_file)) => {
+                builder.tls_client_certs(ClientCerts::Files {
+                    cert_chain_file,
+                    private_key_file,
+                });
+            }
+            (None, None) => {}
+            _ => {
+                return Err(HdbError::Usage(
+                    "The url-options 'tls_client_certificate_file' and 'tls_client_key_file' \
+                    must be used together",
+                ));
+            }
+        }
 
         Ok(builder)
     }
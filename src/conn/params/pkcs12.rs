@@ -0,0 +1,68 @@
+// Reads trust anchors and client identities from PKCS#12 files (.p12, .pfx).
+// Certificates and keys are returned DER encoded, independent of the TLS implementation.
+use p12_keystore::{KeyStore, KeyStoreEntry};
+use secstr::SecStr;
+
+fn read_keystore(file: &str, password: &SecStr) -> std::io::Result<KeyStore> {
+    let data = std::fs::read(file)?;
+    KeyStore::from_pkcs12(&data, password_str(password)?).map_err(|e| {
+        std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            format!("PKCS#12 file {} could not be read, reason: {}", file, e),
+        )
+    })
+}
+
+pub(crate) fn password_str(password: &SecStr) -> std::io::Result<&str> {
+    std::str::from_utf8(password.unsecure()).map_err(|_| {
+        std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            "the password of a PKCS#12 file must be valid UTF-8",
+        )
+    })
+}
+
+// Trusted certificates are taken from the certificate entries, and from the
+// root certificates at the end of the private key chains.
+pub(crate) fn trusted_certificates(file: &str, password: &SecStr) -> std::io::Result<Vec<Vec<u8>>> {
+    let keystore = read_keystore(file, password)?;
+    Ok(keystore
+        .entries()
+        .filter_map(|(alias, entry)| {
+            let o_cert = match entry {
+                KeyStoreEntry::Certificate(cert) => Some(cert),
+                KeyStoreEntry::PrivateKeyChain(key_chain) => match key_chain.chain() {
+                    [_, .., root] => Some(root),
+                    _ => None,
+                },
+            };
+            o_cert.map(|cert| {
+                trace!("Found trust anchor {} in {}", alias, file);
+                cert.as_der().to_vec()
+            })
+        })
+        .collect())
+}
+
+// The client identity is the (first) private key chain.
+pub(crate) fn load_identity(
+    file: &str,
+    password: &SecStr,
+) -> std::io::Result<(Vec<Vec<u8>>, Vec<u8>)> {
+    let keystore = read_keystore(file, password)?;
+    let (alias, key_chain) = keystore.private_key_chain().ok_or_else(|| {
+        std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            format!("PKCS#12 file {} contains no private key", file),
+        )
+    })?;
+    debug!("Using client identity {} from {}", alias, file);
+    Ok((
+        key_chain
+            .chain()
+            .iter()
+            .map(|cert| cert.as_der().to_vec())
+            .collect(),
+        key_chain.key().to_vec(),
+    ))
+}
@@ -0,0 +1,80 @@
+use secstr::SecStr;
+
+/// The kind of proxy through which the connection to the database is established.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum ProxyType {
+    /// A SOCKS5 proxy (RFC 1928), like the SAP Cloud Connector;
+    /// user and password are sent with the username/password authentication (RFC 1929).
+    Socks5,
+    /// An HTTP proxy that supports the CONNECT method;
+    /// user and password are sent with basic authentication.
+    Http,
+}
+impl ProxyType {
+    pub(crate) fn from_url_value(value: &str) -> Option<Self> {
+        match value {
+            "socks5" => Some(Self::Socks5),
+            "http" => Some(Self::Http),
+            _ => None,
+        }
+    }
+}
+impl std::fmt::Display for ProxyType {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::Socks5 => write!(f, "socks5"),
+            Self::Http => write!(f, "http"),
+        }
+    }
+}
+
+// Where and how the proxy is reached.
+#[derive(Clone, Debug)]
+pub(crate) struct ProxyParams {
+    proxy_type: ProxyType,
+    host: String,
+    port: u16,
+    user: Option<String>,
+    password: Option<SecStr>,
+}
+impl ProxyParams {
+    pub fn new(
+        proxy_type: ProxyType,
+        host: String,
+        port: u16,
+        user: Option<String>,
+        password: Option<SecStr>,
+    ) -> Self {
+        Self {
+            proxy_type,
+            host,
+            port,
+            user,
+            password,
+        }
+    }
+
+    pub fn proxy_type(&self) -> ProxyType {
+        self.proxy_type
+    }
+
+    pub fn host(&self) -> &str {
+        &self.host
+    }
+
+    pub fn port(&self) -> u16 {
+        self.port
+    }
+
+    pub fn addr(&self) -> String {
+        format!("{}:{}", self.host, self.port)
+    }
+
+    pub fn user(&self) -> Option<&str> {
+        self.user.as_deref()
+    }
+
+    pub fn password(&self) -> Option<&SecStr> {
+        self.password.as_ref()
+    }
+}
@@ -0,0 +1,291 @@
+#[cfg(feature = "rustls")]
+use rustls::{Certificate, RootCertStore, ServerCertVerified, TLSError, WebPKIVerifier};
+use sha2::{Digest, Sha256};
+use std::sync::Arc;
+#[cfg(feature = "rustls")]
+use webpki::DNSNameRef;
+
+/// Verifies the certificate chain that the server presents during the TLS handshake,
+/// in place of the driver's standard verification against the
+/// [`ServerCerts`](enum.ServerCerts.html).
+///
+/// A `ServerCertVerifier` can be registered with
+/// [`ConnectParamsBuilder::tls_server_cert_verifier`](struct.ConnectParamsBuilder.html#method.tls_server_cert_verifier),
+/// e.g. to apply company-specific rules, without having to switch off the verification with
+/// [`ServerCerts::None`](enum.ServerCerts.html#variant.None).
+///
+/// `ServerCertVerifier` is implemented for suitable closures.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use hdbconnect::{ConnectParams, Connection};
+///
+/// let connect_params = ConnectParams::builder()
+///     .hostname("abcd123")
+///     .port(2222)
+///     .dbuser("MEIER")
+///     .password("schlau")
+///     .tls_server_cert_verifier(|cert_chain: &[&[u8]], host_name: &str| {
+///         // ...check the DER encoded certificates...
+///         if cert_chain.is_empty() {
+///             Err(format!("{} did not present a certificate", host_name))
+///         } else {
+///             Ok(())
+///         }
+///     })
+///     .build()
+///     .unwrap();
+/// let connection = Connection::new(connect_params).unwrap();
+/// ```
+pub trait ServerCertVerifier: Send + Sync {
+    /// Checks the DER encoded certificate chain of the server, which starts with the
+    /// server's own certificate, for the given host name.
+    ///
+    /// # Errors
+    ///
+    /// The returned message is reported to the application as TLS error.
+    fn verify(&self, cert_chain: &[&[u8]], host_name: &str) -> Result<(), String>;
+}
+impl<F> ServerCertVerifier for F
+where
+    F: Fn(&[&[u8]], &str) -> Result<(), String> + Send + Sync,
+{
+    fn verify(&self, cert_chain: &[&[u8]], host_name: &str) -> Result<(), String> {
+        self(cert_chain, host_name)
+    }
+}
+
+// A shareable ServerCertVerifier, which allows ConnectParams and ConnectParamsBuilder
+// to keep their derived trait implementations.
+#[derive(Clone)]
+pub(crate) struct AmServerCertVerifier(Arc<dyn ServerCertVerifier>);
+impl AmServerCertVerifier {
+    pub fn new<V: ServerCertVerifier + 'static>(verifier: V) -> Self {
+        Self(Arc::new(verifier))
+    }
+
+    // Only rustls exposes the server's certificate chain.
+    #[cfg_attr(not(feature = "rustls"), allow(dead_code))]
+    pub fn verify(&self, cert_chain: &[&[u8]], host_name: &str) -> Result<(), String> {
+        self.0.verify(cert_chain, host_name)
+    }
+}
+impl std::fmt::Debug for AmServerCertVerifier {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "ServerCertVerifier {{ ... }}")
+    }
+}
+impl PartialEq for AmServerCertVerifier {
+    fn eq(&self, other: &Self) -> bool {
+        std::ptr::eq(
+            Arc::as_ptr(&self.0).cast::<u8>(),
+            Arc::as_ptr(&other.0).cast::<u8>(),
+        )
+    }
+}
+
+// Replaces rustls' standard verification if the verification is switched off,
+// done by the application, complemented with public key pins, or done against
+// another host name than the one the session was opened for.
+#[cfg(feature = "rustls")]
+pub(crate) struct CertVerification {
+    insecure: bool,
+    o_verifier: Option<AmServerCertVerifier>,
+    public_key_pins: Vec<[u8; 32]>,
+    o_host_name: Option<String>,
+}
+#[cfg(feature = "rustls")]
+impl CertVerification {
+    pub fn new(
+        insecure: bool,
+        o_verifier: Option<AmServerCertVerifier>,
+        public_key_pins: Vec<[u8; 32]>,
+        o_host_name: Option<String>,
+    ) -> Self {
+        Self {
+            insecure,
+            o_verifier,
+            public_key_pins,
+            o_host_name,
+        }
+    }
+
+    // The server's own certificate must have one of the pinned public keys.
+    fn check_public_key_pins(&self, presented_certs: &[Certificate]) -> Result<(), TLSError> {
+        let cert_chain: Vec<&[u8]> = presented_certs
+            .iter()
+            .map(|cert| cert.0.as_slice())
+            .collect();
+        if matches_public_key_pins(&self.public_key_pins, &cert_chain) {
+            Ok(())
+        } else {
+            Err(TLSError::General(
+                "the server did not present any of the pinned public keys".to_string(),
+            ))
+        }
+    }
+}
+#[cfg(feature = "rustls")]
+impl rustls::ServerCertVerifier for CertVerification {
+    fn verify_server_cert(
+        &self,
+        roots: &RootCertStore,
+        presented_certs: &[Certificate],
+        dns_name: DNSNameRef<'_>,
+        ocsp: &[u8],
+    ) -> Result<ServerCertVerified, TLSError> {
+        let dns_name = match self.o_host_name {
+            Some(ref host_name) => DNSNameRef::try_from_ascii_str(host_name).map_err(|_| {
+                TLSError::General(format!("Cannot use {} for DNSNameRef", host_name))
+            })?,
+            None => dns_name,
+        };
+        if let Some(ref verifier) = self.o_verifier {
+            let cert_chain: Vec<&[u8]> = presented_certs
+                .iter()
+                .map(|cert| cert.0.as_slice())
+                .collect();
+            let host_name: &str = dns_name.into();
+            verifier
+                .verify(&cert_chain, host_name)
+                .map_err(TLSError::General)?;
+        } else if !self.insecure {
+            WebPKIVerifier::new().verify_server_cert(roots, presented_certs, dns_name, ocsp)?;
+        }
+        self.check_public_key_pins(presented_certs)?;
+        Ok(ServerCertVerified::assertion())
+    }
+}
+
+// True if there are no pins, or if the first of the DER encoded certificates has one of
+// the pinned public keys.
+//
+// Only the server's own certificate is considered, because its key is the one that is proven
+// in the handshake; the other certificates of the chain can be appended by anybody.
+pub(crate) fn matches_public_key_pins(public_key_pins: &[[u8; 32]], cert_chain: &[&[u8]]) -> bool {
+    public_key_pins.is_empty()
+        || cert_chain.first().is_some_and(|cert| {
+            subject_public_key_info(cert).is_some_and(|spki| {
+                let digest: [u8; 32] = Sha256::digest(spki).into();
+                public_key_pins.contains(&digest)
+            })
+        })
+}
+
+// Parses a public key pin, i.e., the hex encoded SHA-256 digest of a
+// DER encoded SubjectPublicKeyInfo; colons between the bytes are ignored.
+pub(crate) fn parse_public_key_pin(pin: &str) -> Option<[u8; 32]> {
+    let mut digest = [0_u8; 32];
+    hex::decode_to_slice(pin.replace(':', ""), &mut digest).ok()?;
+    Some(digest)
+}
+
+// Returns the DER encoded SubjectPublicKeyInfo of a DER encoded X.509 certificate.
+fn subject_public_key_info(cert: &[u8]) -> Option<&[u8]> {
+    let (_, certificate, _) = split_der(cert)?;
+    let (_, mut tbs_certificate, _) = split_der(certificate)?;
+    // the version is optional and has the context-specific tag [0]
+    let (tag, _, rest) = split_der(tbs_certificate)?;
+    if tag == 0xA0 {
+        tbs_certificate = rest;
+    }
+    // skip serialNumber, signature, issuer, validity, subject
+    for _ in 0..5 {
+        let (_, _, rest) = split_der(tbs_certificate)?;
+        tbs_certificate = rest;
+    }
+    let (tag, _, rest) = split_der(tbs_certificate)?;
+    if tag == 0x30 {
+        Some(&tbs_certificate[..tbs_certificate.len() - rest.len()])
+    } else {
+        None
+    }
+}
+
+// Splits off the first DER element from the input, and returns its tag, its content,
+// and the remainder of the input.
+fn split_der(input: &[u8]) -> Option<(u8, &[u8], &[u8])> {
+    let tag = *input.first()?;
+    let first_length_byte = *input.get(1)?;
+    let (header_length, content_length) = if first_length_byte < 0x80 {
+        (2, usize::from(first_length_byte))
+    } else {
+        let n = usize::from(first_length_byte & 0x7F);
+        if n == 0 || n > 4 {
+            return None;
+        }
+        let length = input
+            .get(2..2 + n)?
+            .iter()
+            .fold(0_usize, |acc, b| (acc << 8) | usize::from(*b));
+        (2 + n, length)
+    };
+    let end = header_length.checked_add(content_length)?;
+    let content = input.get(header_length..end)?;
+    Some((tag, content, &input[end..]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{matches_public_key_pins, parse_public_key_pin, subject_public_key_info};
+    use sha2::{Digest, Sha256};
+
+    // a minimal certificate structure: version, serialNumber, signature, issuer,
+    // validity, subject, subjectPublicKeyInfo
+    fn certificate(spki: &[u8]) -> Vec<u8> {
+        let mut tbs = vec![
+            0xA0, 0x03, 0x02, 0x01, 0x02, // version
+            0x02, 0x01, 0x01, // serialNumber
+            0x30, 0x00, // signature
+            0x30, 0x00, // issuer
+            0x30, 0x00, // validity
+            0x30, 0x00, // subject
+        ];
+        tbs.extend_from_slice(spki);
+        let mut tbs_certificate = vec![0x30, tbs.len() as u8];
+        tbs_certificate.extend_from_slice(&tbs);
+        let mut certificate = vec![0x30, 0x81, tbs_certificate.len() as u8];
+        certificate.extend_from_slice(&tbs_certificate);
+        certificate
+    }
+
+    #[test]
+    fn test_subject_public_key_info() {
+        let spki = [0x30, 0x03, 0x02, 0x01, 0x07];
+        let certificate = certificate(&spki);
+
+        assert_eq!(Some(&spki[..]), subject_public_key_info(&certificate));
+        assert_eq!(None, subject_public_key_info(&certificate[..10]));
+    }
+
+    #[test]
+    fn test_parse_public_key_pin() {
+        let pin = "0123456789abcdef0123456789ABCDEF0123456789abcdef0123456789abcdef";
+        assert_eq!(
+            Some(0x01),
+            parse_public_key_pin(pin).map(|digest| digest[0])
+        );
+        assert!(parse_public_key_pin(&pin[2..]).is_none());
+        assert!(parse_public_key_pin("01:23").is_none());
+    }
+
+    #[test]
+    fn test_public_key_pins_apply_to_server_certificate_only() {
+        let server_spki = [0x30, 0x03, 0x02, 0x01, 0x07];
+        let pinned_spki = [0x30, 0x03, 0x02, 0x01, 0x08];
+        let server_cert = certificate(&server_spki);
+        let pinned_cert = certificate(&pinned_spki);
+        let pins: [[u8; 32]; 1] = [Sha256::digest(&pinned_spki).into()];
+
+        assert!(matches_public_key_pins(&pins, &[&pinned_cert]));
+        assert!(matches_public_key_pins(&[], &[&server_cert]));
+        assert!(!matches_public_key_pins(&pins, &[&server_cert]));
+        assert!(!matches_public_key_pins(&pins, &[]));
+        // an attacker must not get through by appending the pinned certificate to the chain
+        assert!(!matches_public_key_pins(
+            &pins,
+            &[&server_cert, &pinned_cert]
+        ));
+    }
+}
@@ -0,0 +1,298 @@
+// Reads the connection data of SAP HANA (Cloud) instances from service bindings,
+// as Cloud Foundry provides them in VCAP_SERVICES, or as servicebinding.io mounts them
+// into Kubernetes pods.
+use crate::{ConnectParamsBuilder, HdbError, HdbResult, ServerCerts};
+use serde_json::Value;
+use std::path::{Path, PathBuf};
+
+const VCAP_SERVICES: &str = "VCAP_SERVICES";
+const HANA_CLOUD: &str = "hana-cloud";
+const HANA_SERVICES: [&str; 2] = ["hana", HANA_CLOUD];
+
+pub(crate) fn from_vcap_services() -> HdbResult<ConnectParamsBuilder> {
+    let vcap_services = std::env::var(VCAP_SERVICES).map_err(|e| {
+        conn_params_error(format!(
+            "Environment variable {} not found, reason: {}",
+            VCAP_SERVICES, e
+        ))
+    })?;
+    from_vcap_json(&vcap_services)
+}
+
+// VCAP_SERVICES maps the service labels to lists of bindings; the first binding of a
+// HANA service is used, also if it is a user-provided service that is tagged with "hana".
+fn from_vcap_json(vcap_services: &str) -> HdbResult<ConnectParamsBuilder> {
+    let services: Value =
+        serde_json::from_str(vcap_services).map_err(|e| HdbError::conn_params(Box::new(e)))?;
+    let (binding, is_hana_cloud) = services
+        .as_object()
+        .into_iter()
+        .flatten()
+        .filter_map(|(label, bindings)| bindings.as_array().map(|bindings| (label, bindings)))
+        .flat_map(|(label, bindings)| bindings.iter().map(move |binding| (label, binding)))
+        .find_map(|(label, binding)| {
+            if is_hana_service(label)
+                || binding["label"].as_str().is_some_and(is_hana_service)
+                || binding["tags"]
+                    .as_array()
+                    .is_some_and(|tags| tags.iter().any(|tag| tag == "hana"))
+            {
+                let is_hana_cloud =
+                    label == HANA_CLOUD || binding["label"].as_str() == Some(HANA_CLOUD);
+                Some((binding, is_hana_cloud))
+            } else {
+                None
+            }
+        })
+        .ok_or_else(|| {
+            conn_params_error(format!(
+                "{} contains no HANA service binding",
+                VCAP_SERVICES
+            ))
+        })?;
+    debug!(
+        "Using service binding {:?} from {}",
+        binding["name"].as_str().unwrap_or_default(),
+        VCAP_SERVICES
+    );
+
+    let credentials = &binding["credentials"];
+    let value = |name: &str| match &credentials[name] {
+        Value::String(s) => Some(s.clone()),
+        Value::Number(n) => Some(n.to_string()),
+        Value::Bool(b) => Some(b.to_string()),
+        _ => None,
+    };
+    Credentials {
+        host: value("host"),
+        port: value("port"),
+        user: value("user").or_else(|| value("username")),
+        password: value("password"),
+        schema: value("schema"),
+        certificate: value("certificate"),
+        encrypt: value("encrypt"),
+        is_hana_cloud,
+    }
+    .into_builder()
+}
+
+// The given directory is either the binding itself, or contains the bindings as
+// subdirectories (like $SERVICE_BINDING_ROOT); a binding is identified by its
+// type or label.
+pub(crate) fn from_service_binding_dir(path: &Path) -> HdbResult<ConnectParamsBuilder> {
+    let binding_dir = if is_hana_binding_dir(path) {
+        path.to_path_buf()
+    } else {
+        let mut sub_dirs: Vec<PathBuf> = std::fs::read_dir(path)
+            .map_err(|e| HdbError::conn_params(Box::new(e)))?
+            .filter_map(Result::ok)
+            .map(|dir_entry| dir_entry.path())
+            .filter(|path| path.is_dir())
+            .collect();
+        sub_dirs.sort();
+        sub_dirs
+            .into_iter()
+            .find(|dir| is_hana_binding_dir(dir))
+            .ok_or_else(|| {
+                conn_params_error(format!(
+                    "{} contains no HANA service binding",
+                    path.display()
+                ))
+            })?
+    };
+    debug!("Using service binding {}", binding_dir.display());
+
+    let value = |name: &str| read_binding_entry(&binding_dir, name);
+    Credentials {
+        host: value("host"),
+        port: value("port"),
+        user: value("user").or_else(|| value("username")),
+        password: value("password"),
+        schema: value("schema"),
+        certificate: value("certificate"),
+        encrypt: value("encrypt"),
+        is_hana_cloud: value("type")
+            .or_else(|| value("label"))
+            .is_some_and(|service| service == HANA_CLOUD),
+    }
+    .into_builder()
+}
+
+fn is_hana_service(label: &str) -> bool {
+    HANA_SERVICES.contains(&label)
+}
+
+fn is_hana_binding_dir(dir: &Path) -> bool {
+    read_binding_entry(dir, "type")
+        .or_else(|| read_binding_entry(dir, "label"))
+        .is_some_and(|service| is_hana_service(&service))
+}
+
+// Kubernetes secrets are mounted as one file per entry.
+fn read_binding_entry(dir: &Path, name: &str) -> Option<String> {
+    std::fs::read_to_string(dir.join(name))
+        .ok()
+        .map(|value| value.trim_end_matches(&['\n', '\r'][..]).to_string())
+}
+
+// Only rustls supports the webpki root certificates.
+fn public_root_certificates() -> ServerCerts {
+    if cfg!(feature = "native-tls") || !cfg!(feature = "rustls") {
+        ServerCerts::SystemStore
+    } else {
+        ServerCerts::RootCertificates
+    }
+}
+
+fn conn_params_error(message: String) -> HdbError {
+    HdbError::conn_params(message.into())
+}
+
+struct Credentials {
+    host: Option<String>,
+    port: Option<String>,
+    user: Option<String>,
+    password: Option<String>,
+    schema: Option<String>,
+    certificate: Option<String>,
+    encrypt: Option<String>,
+    is_hana_cloud: bool,
+}
+impl Credentials {
+    fn into_builder(self) -> HdbResult<ConnectParamsBuilder> {
+        let missing = |name: &str| conn_params_error(format!("service binding has no {}", name));
+        let port = self.port.ok_or_else(|| missing("port"))?;
+        let mut builder = ConnectParamsBuilder::new();
+        builder
+            .hostname(self.host.ok_or_else(|| missing("host"))?)
+            .port(port.parse::<u16>().map_err(|_| {
+                conn_params_error(format!("service binding has invalid port {:?}", port))
+            })?)
+            .dbuser(self.user.ok_or_else(|| missing("user"))?)
+            .password(self.password.ok_or_else(|| missing("password"))?);
+        if let Some(schema) = self.schema {
+            builder.schema(schema);
+        }
+        // SAP HANA Cloud only accepts TLS connections; its server certificates are signed
+        // by a public root CA, so without a certificate in the binding the public roots are used
+        if let Some(certificate) = self.certificate {
+            builder.tls_with(ServerCerts::Direct(certificate));
+        } else if self.is_hana_cloud
+            || self
+                .encrypt
+                .is_some_and(|encrypt| encrypt.eq_ignore_ascii_case("true"))
+        {
+            builder.tls_with(public_root_certificates());
+        }
+        Ok(builder)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{from_service_binding_dir, from_vcap_json, public_root_certificates};
+    use crate::ServerCerts;
+
+    #[test]
+    fn test_from_vcap_json() {
+        let vcap_services = r#"{
+            "xsuaa": [{"name": "uaa", "credentials": {"clientid": "abc"}}],
+            "hana": [{
+                "name": "my-hdi-container",
+                "label": "hana",
+                "tags": ["hana", "database", "relational"],
+                "credentials": {
+                    "host": "abcd123.hana.example.com",
+                    "port": "30015",
+                    "user": "MEIER_RT",
+                    "password": "schLau",
+                    "schema": "MY_SCHEMA",
+                    "certificate": "-----BEGIN CERTIFICATE-----\nMIIB\n-----END CERTIFICATE-----"
+                }
+            }]
+        }"#;
+        let builder = from_vcap_json(vcap_services).unwrap();
+        assert_eq!(Some("abcd123.hana.example.com"), builder.get_hostname());
+        assert_eq!(Some(30015), builder.get_port());
+        assert_eq!(Some("MEIER_RT"), builder.get_dbuser());
+        assert_eq!(Some("MY_SCHEMA"), builder.get_schema());
+        assert_eq!(
+            &vec![ServerCerts::Direct(
+                "-----BEGIN CERTIFICATE-----\nMIIB\n-----END CERTIFICATE-----".to_string()
+            )],
+            builder.get_server_certs()
+        );
+        let params = builder.build().unwrap();
+        assert_eq!(b"schLau", params.password().unsecure());
+
+        // user-provided services are recognized by their tag, and the port can be a number
+        let builder = from_vcap_json(
+            r#"{"user-provided": [{"label": "user-provided", "tags": ["hana"],
+                "credentials": {"host": "abcd123", "port": 443, "user": "MEIER",
+                "password": "schLau"}}]}"#,
+        )
+        .unwrap();
+        assert_eq!(Some(443), builder.get_port());
+        assert!(builder.get_schema().is_none());
+        assert!(builder.get_server_certs().is_empty());
+
+        // without a certificate, HANA Cloud and requested encryption use the public roots
+        let builder = from_vcap_json(
+            r#"{"hana-cloud": [{"credentials": {"host": "abcd123", "port": 443,
+                "user": "MEIER", "password": "schLau"}}]}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            &vec![public_root_certificates()],
+            builder.get_server_certs()
+        );
+        let builder = from_vcap_json(
+            r#"{"hana": [{"credentials": {"host": "abcd123", "port": 30015,
+                "user": "MEIER", "password": "schLau", "encrypt": true}}]}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            &vec![public_root_certificates()],
+            builder.get_server_certs()
+        );
+
+        assert!(from_vcap_json(r#"{"xsuaa": [{"credentials": {}}]}"#).is_err());
+        assert!(from_vcap_json(r#"{"hana": [{"credentials": {"host": "abcd123"}}]}"#).is_err());
+        assert!(from_vcap_json("no json").is_err());
+    }
+
+    #[test]
+    fn test_from_service_binding_dir() {
+        let root = std::env::temp_dir().join(format!("hdbconnect_bindings_{}", std::process::id()));
+        let binding = root.join("hana-db");
+        std::fs::create_dir_all(&binding).unwrap();
+        std::fs::create_dir_all(root.join("other")).unwrap();
+        for (name, value) in &[
+            ("type", "hana\n"),
+            ("host", "abcd123.hana.example.com"),
+            ("port", "443"),
+            ("username", "MEIER_RT"),
+            ("password", "schLau"),
+            (
+                "certificate",
+                "-----BEGIN CERTIFICATE-----\nMIIB\n-----END CERTIFICATE-----\n",
+            ),
+        ] {
+            std::fs::write(binding.join(name), value).unwrap();
+        }
+        std::fs::write(root.join("other").join("type"), "postgresql").unwrap();
+
+        for dir in &[&root, &binding] {
+            let builder = from_service_binding_dir(dir).unwrap();
+            assert_eq!(Some("abcd123.hana.example.com"), builder.get_hostname());
+            assert_eq!(Some(443), builder.get_port());
+            assert_eq!(Some("MEIER_RT"), builder.get_dbuser());
+            assert!(builder.get_schema().is_none());
+            assert_eq!(1, builder.get_server_certs().len());
+        }
+        assert!(from_service_binding_dir(&root.join("other")).is_err());
+        assert!(from_service_binding_dir(&root.join("missing")).is_err());
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -21,6 +21,38 @@ impl Default for SessionState {
     }
 }
 impl SessionState {
+    // Whether the session has changes that are not yet committed.
+    pub fn has_uncommitted_changes(&self) -> bool {
+        matches!(self.ta_state, TransactionState::WriteTransaction)
+    }
+
+    // The state of a new session, in which the client-controlled settings
+    // of this session still have to be restored.
+    pub fn for_new_session(&self) -> Self {
+        Self {
+            isolation_level: self.isolation_level,
+            ddl_commit_mode: self.ddl_commit_mode,
+            ..Self::default()
+        }
+    }
+
+    // The statements that bring a new session to the state of this session.
+    pub fn restore_statements(&self) -> Vec<String> {
+        let mut statements = Vec::new();
+        // 0 means that the server did not report an isolation level yet
+        match self.isolation_level {
+            0 => {}
+            1 => statements.push("SET TRANSACTION ISOLATION LEVEL READ COMMITTED".to_string()),
+            2 => statements.push("SET TRANSACTION ISOLATION LEVEL REPEATABLE READ".to_string()),
+            3 => statements.push("SET TRANSACTION ISOLATION LEVEL SERIALIZABLE".to_string()),
+            level => warn!("cannot restore unknown isolation level {}", level),
+        }
+        if !self.ddl_commit_mode {
+            statements.push("SET TRANSACTION AUTOCOMMIT DDL OFF".to_string());
+        }
+        statements
+    }
+
     pub fn update(&mut self, transaction_flags: TransactionFlags) {
         for (id, value) in transaction_flags {
             #[allow(clippy::cast_sign_loss)]
@@ -1,31 +1,118 @@
+#[cfg(feature = "async")]
+mod async_tcp_client;
+#[cfg(all(feature = "async", feature = "native-tls"))]
+mod native_tls_async_tcp_client;
+#[cfg(feature = "native-tls")]
+mod native_tls_sync_tcp_client;
+#[cfg(not(any(feature = "rustls", feature = "native-tls")))]
+mod no_tls_tcp_client;
+#[cfg(feature = "async")]
+mod plain_async_tcp_client;
 mod plain_sync_tcp_client;
+mod proxy;
+#[cfg(all(feature = "async", feature = "rustls", not(feature = "native-tls")))]
+mod tls_async_tcp_client;
+#[cfg(all(feature = "rustls", not(feature = "native-tls")))]
 mod tls_sync_tcp_client;
 
-use crate::ConnectParams;
+use crate::protocol::util;
+use crate::{ConnectParams, Transport};
+#[cfg(feature = "async")]
+pub(crate) use async_tcp_client::{AsyncChannel, AsyncTcpClient};
 use chrono::Local;
+#[cfg(feature = "native-tls")]
+use native_tls_sync_tcp_client::TlsSyncTcpClient;
+#[cfg(not(any(feature = "rustls", feature = "native-tls")))]
+use no_tls_tcp_client::TlsSyncTcpClient;
 use plain_sync_tcp_client::PlainSyncTcpClient;
+use socket2::{SockRef, TcpKeepalive};
+use std::io::{Read, Write};
+use std::net::{TcpStream, ToSocketAddrs};
+use std::sync::{Arc, Mutex, MutexGuard};
+#[cfg(all(feature = "rustls", not(feature = "native-tls")))]
 use tls_sync_tcp_client::TlsSyncTcpClient;
 
-// A buffered tcp connection, with or without TLS.
+// A buffered connection, with or without TLS.
 #[derive(Debug)]
 pub(crate) enum TcpClient {
-    // A buffered tcp connection without TLS.
-    SyncPlain(PlainSyncTcpClient),
-    // A buffered blocking tcp connection with TLS.
-    SyncTls(TlsSyncTcpClient),
+    // A buffered blocking connection, with or without TLS.
+    Sync(SyncTcpClient),
+    // A non-blocking tcp connection, with or without TLS.
+    #[cfg(feature = "async")]
+    Async(AsyncTcpClient),
 }
 impl TcpClient {
-    // Constructs a buffered tcp connection, with or without TLS,
+    // Constructs a buffered blocking connection, with or without TLS,
     // depending on the given connect parameters.
-    pub fn try_new(params: ConnectParams) -> std::io::Result<Self> {
+    //
+    // If several hosts are configured, they are tried in turn, starting with the current one.
+    pub fn try_new(params: &ConnectParams) -> std::io::Result<Self> {
+        let mut o_error = None;
+        for n in 0..params.addrs().len() {
+            match Self::try_new_with_host(params.with_nth_next_host(n)) {
+                Ok(tcp_conn) => return Ok(tcp_conn),
+                Err(e) => o_error = Some(e),
+            }
+        }
+        Err(o_error.unwrap_or_else(|| util::io_error("no host to connect to")))
+    }
+
+    fn try_new_with_host(params: ConnectParams) -> std::io::Result<Self> {
         let start = Local::now();
         trace!("TcpClient: Connecting to {:?})", params.addr());
 
+        let addr = params.addr().to_string();
         let tcp_conn = if params.use_tls() {
-            Self::SyncTls(TlsSyncTcpClient::try_new(params)?)
+            TlsSyncTcpClient::try_new(params).map(SyncTcpClient::Tls)
         } else {
-            Self::SyncPlain(PlainSyncTcpClient::try_new(params)?)
-        };
+            PlainSyncTcpClient::try_new(params).map(SyncTcpClient::Plain)
+        }
+        .map(Self::Sync)
+        .map_err(|e| {
+            warn!("Connecting to {} failed with {}", addr, e);
+            e
+        })?;
+
+        trace!(
+            "Connection of type {} is initialized ({} µs)",
+            tcp_conn.s_type(),
+            Local::now()
+                .signed_duration_since(start)
+                .num_microseconds()
+                .unwrap_or(-1)
+        );
+        Ok(tcp_conn)
+    }
+
+    // Constructs a non-blocking tcp connection, with or without TLS,
+    // depending on the given connect parameters.
+    //
+    // If several hosts are configured, they are tried in turn, starting with the current one.
+    #[cfg(feature = "async")]
+    pub async fn try_new_async(params: &ConnectParams) -> std::io::Result<Self> {
+        let mut o_error = None;
+        for n in 0..params.addrs().len() {
+            match Self::try_new_async_with_host(params.with_nth_next_host(n)).await {
+                Ok(tcp_conn) => return Ok(tcp_conn),
+                Err(e) => o_error = Some(e),
+            }
+        }
+        Err(o_error.unwrap_or_else(|| util::io_error("no host to connect to")))
+    }
+
+    #[cfg(feature = "async")]
+    async fn try_new_async_with_host(params: ConnectParams) -> std::io::Result<Self> {
+        let start = Local::now();
+        trace!(
+            "TcpClient: Connecting asynchronously to {:?})",
+            params.addr()
+        );
+
+        let addr = params.addr().to_string();
+        let tcp_conn = Self::Async(AsyncTcpClient::try_new(params).await.map_err(|e| {
+            warn!("Connecting to {} failed with {}", addr, e);
+            e
+        })?);
 
         trace!(
             "Connection of type {} is initialized ({} µs)",
@@ -41,18 +128,246 @@ impl TcpClient {
     // Returns a descriptor of the chosen type
     pub fn s_type(&self) -> &'static str {
         match self {
-            Self::SyncPlain(_) => "Plain TCP",
-            Self::SyncTls(_) => "TLS",
+            Self::Sync(client) => client.s_type(),
+            #[cfg(feature = "async")]
+            Self::Async(client) => client.s_type(),
+        }
+    }
+
+    // Returns the blocking client, or None for a non-blocking connection.
+    #[cfg_attr(not(feature = "async"), allow(clippy::unnecessary_wraps))]
+    pub fn sync_client(&mut self) -> Option<&mut SyncTcpClient> {
+        match self {
+            Self::Sync(client) => Some(client),
+            #[cfg(feature = "async")]
+            Self::Async(_) => None,
         }
     }
 
     pub fn connect_params(&self) -> &ConnectParams {
         match self {
-            Self::SyncPlain(client) => client.connect_params(),
-            Self::SyncTls(client) => client.connect_params(),
+            Self::Sync(client) => client.connect_params(),
+            #[cfg(feature = "async")]
+            Self::Async(client) => client.connect_params(),
+        }
+    }
+}
+
+// A buffered blocking connection, with or without TLS,
+// over a tcp connection or over a custom transport.
+#[derive(Debug)]
+#[cfg_attr(
+    not(any(feature = "rustls", feature = "native-tls")),
+    allow(clippy::large_enum_variant)
+)]
+pub(crate) enum SyncTcpClient {
+    Plain(PlainSyncTcpClient),
+    Tls(TlsSyncTcpClient),
+}
+impl SyncTcpClient {
+    fn s_type(&self) -> &'static str {
+        match (self, self.connect_params().has_transport_factory()) {
+            (Self::Plain(_), false) => "Plain TCP",
+            (Self::Tls(_), false) => "TLS",
+            (Self::Plain(_), true) => "Plain custom transport",
+            (Self::Tls(_), true) => "TLS over custom transport",
+        }
+    }
+
+    fn connect_params(&self) -> &ConnectParams {
+        match self {
+            Self::Plain(client) => client.connect_params(),
+            Self::Tls(client) => client.connect_params(),
+        }
+    }
+
+    pub fn writer(&mut self) -> &mut dyn Write {
+        match self {
+            Self::Plain(client) => client.writer(),
+            Self::Tls(client) => client.writer(),
+        }
+    }
+
+    pub fn reader(&mut self) -> &mut dyn Read {
+        match self {
+            Self::Plain(client) => client.reader(),
+            Self::Tls(client) => client.reader(),
+        }
+    }
+}
+
+// Opens the blocking byte stream to the database, either with the configured
+// TransportFactory or as tcp connection.
+fn open_transport(params: &ConnectParams) -> std::io::Result<SharedTransport> {
+    let transport = match params.transport_factory() {
+        Some(factory) => factory.connect(params)?,
+        None => Box::new(TcpTransport::new(connect_tcpstream(params)?, params)),
+    };
+    Ok(SharedTransport(Arc::new(Mutex::new(transport))))
+}
+
+// Allows using a transport with separate objects for reading and writing.
+#[derive(Clone)]
+pub(crate) struct SharedTransport(Arc<Mutex<Box<dyn Transport>>>);
+impl SharedTransport {
+    fn lock(&self) -> std::io::Result<MutexGuard<'_, Box<dyn Transport>>> {
+        self.0
+            .lock()
+            .map_err(|e| util::io_error(format!("transport is poisoned: {}", e)))
+    }
+}
+impl Read for SharedTransport {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        self.lock()?.read(buf)
+    }
+}
+impl Write for SharedTransport {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.lock()?.write(buf)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.lock()?.flush()
+    }
+}
+impl std::fmt::Debug for SharedTransport {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "Transport {{ ... }}")
+    }
+}
+
+// A blocking tcp connection with the configured read and write timeouts.
+//
+// Blocking sockets report an expired read or write timeout with WouldBlock on unix,
+// which is translated here into TimedOut.
+struct TcpTransport {
+    tcpstream: TcpStream,
+    has_read_timeout: bool,
+    has_write_timeout: bool,
+}
+impl TcpTransport {
+    fn new(tcpstream: TcpStream, params: &ConnectParams) -> Self {
+        Self {
+            tcpstream,
+            has_read_timeout: params.read_timeout().is_some(),
+            has_write_timeout: params.write_timeout().is_some(),
         }
     }
 }
+impl Read for TcpTransport {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let has_timeout = self.has_read_timeout;
+        self.tcpstream
+            .read(buf)
+            .map_err(|e| timed_out_if_would_block(e, has_timeout))
+    }
+}
+impl Write for TcpTransport {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        let has_timeout = self.has_write_timeout;
+        self.tcpstream
+            .write(buf)
+            .map_err(|e| timed_out_if_would_block(e, has_timeout))
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        let has_timeout = self.has_write_timeout;
+        self.tcpstream
+            .flush()
+            .map_err(|e| timed_out_if_would_block(e, has_timeout))
+    }
+}
+
+fn timed_out_if_would_block(e: std::io::Error, has_timeout: bool) -> std::io::Error {
+    if has_timeout && e.kind() == std::io::ErrorKind::WouldBlock {
+        std::io::Error::new(std::io::ErrorKind::TimedOut, e)
+    } else {
+        e
+    }
+}
+
+// Opens a blocking tcp connection to the database, directly or through the configured proxy,
+// and applies the configured timeouts.
+fn connect_tcpstream(params: &ConnectParams) -> std::io::Result<TcpStream> {
+    let addr = match params.proxy() {
+        Some(proxy) => proxy.addr(),
+        None => params.addr().to_string(),
+    };
+    let mut tcpstream = match params.connect_timeout() {
+        Some(timeout) => {
+            // connect_timeout() requires a resolved address, so we try all we get
+            let mut o_error = None;
+            let mut o_tcpstream = None;
+            for socket_addr in addr.to_socket_addrs()? {
+                match TcpStream::connect_timeout(&socket_addr, timeout) {
+                    Ok(tcpstream) => {
+                        o_tcpstream = Some(tcpstream);
+                        break;
+                    }
+                    Err(e) => o_error = Some(e),
+                }
+            }
+            match (o_tcpstream, o_error) {
+                (Some(tcpstream), _) => tcpstream,
+                (None, Some(e)) => return Err(e),
+                (None, None) => {
+                    return Err(std::io::Error::new(
+                        std::io::ErrorKind::InvalidInput,
+                        format!("Could not resolve {}", addr),
+                    ))
+                }
+            }
+        }
+        None => TcpStream::connect(&addr)?,
+    };
+    tcpstream.set_read_timeout(params.read_timeout())?;
+    tcpstream.set_write_timeout(params.write_timeout())?;
+    set_tcp_keepalive(&SockRef::from(&tcpstream), params)?;
+    if let Some(proxy) = params.proxy() {
+        proxy::handshake(&mut tcpstream, proxy, params.addr())?;
+    }
+    Ok(tcpstream)
+}
+
+// Enables TCP keepalive on the socket, if requested in the connect parameters.
+fn set_tcp_keepalive(socket: &SockRef, params: &ConnectParams) -> std::io::Result<()> {
+    if !params.tcp_keepalive() {
+        return Ok(());
+    }
+    let mut keepalive = TcpKeepalive::new();
+    if let Some(idle) = params.tcp_keepalive_idle() {
+        keepalive = keepalive.with_time(idle);
+    }
+    #[cfg(any(
+        target_os = "android",
+        target_os = "freebsd",
+        target_os = "ios",
+        target_os = "linux",
+        target_os = "macos",
+        target_os = "netbsd",
+        target_os = "windows",
+    ))]
+    {
+        if let Some(interval) = params.tcp_keepalive_interval() {
+            keepalive = keepalive.with_interval(interval);
+        }
+    }
+    #[cfg(any(
+        target_os = "android",
+        target_os = "freebsd",
+        target_os = "ios",
+        target_os = "linux",
+        target_os = "macos",
+        target_os = "netbsd",
+    ))]
+    {
+        if let Some(count) = params.tcp_keepalive_count() {
+            keepalive = keepalive.with_retries(count);
+        }
+    }
+    socket.set_tcp_keepalive(&keepalive)
+}
+
 impl Drop for TcpClient {
     fn drop(&mut self) {
         trace!("Drop of TcpClient")
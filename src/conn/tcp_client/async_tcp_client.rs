@@ -0,0 +1,183 @@
+#[cfg(feature = "native-tls")]
+use super::native_tls_async_tcp_client::TlsAsyncTcpClient;
+#[cfg(not(any(feature = "rustls", feature = "native-tls")))]
+use super::no_tls_tcp_client::TlsAsyncTcpClient;
+use super::plain_async_tcp_client::PlainAsyncTcpClient;
+#[cfg(all(feature = "rustls", not(feature = "native-tls")))]
+use super::tls_async_tcp_client::TlsAsyncTcpClient;
+use crate::protocol::MESSAGE_HEADER_SIZE;
+use crate::ConnectParams;
+use byteorder::{ByteOrder, LittleEndian};
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Mutex;
+
+// A non-blocking tcp connection, with or without TLS.
+//
+// The channel is protected by its own (async) mutex, so that it can be used
+// across await points without holding the lock on the ConnectionCore.
+#[derive(Debug)]
+pub(crate) struct AsyncTcpClient {
+    params: ConnectParams,
+    am_channel: Arc<Mutex<AsyncChannel>>,
+}
+impl AsyncTcpClient {
+    pub async fn try_new(params: ConnectParams) -> std::io::Result<Self> {
+        let stream = if params.use_tls() {
+            AsyncStream::Tls(Box::new(
+                with_timeout(
+                    params.connect_timeout(),
+                    TlsAsyncTcpClient::try_new(&params),
+                )
+                .await?,
+            ))
+        } else {
+            AsyncStream::Plain(
+                with_timeout(
+                    params.connect_timeout(),
+                    PlainAsyncTcpClient::try_new(&params),
+                )
+                .await?,
+            )
+        };
+        let channel = AsyncChannel {
+            stream,
+            read_timeout: params.read_timeout(),
+            write_timeout: params.write_timeout(),
+            is_out_of_sync: false,
+        };
+        Ok(Self {
+            params,
+            am_channel: Arc::new(Mutex::new(channel)),
+        })
+    }
+
+    pub fn s_type(&self) -> &'static str {
+        if self.params.use_tls() {
+            "async TLS"
+        } else {
+            "async Plain TCP"
+        }
+    }
+
+    pub fn connect_params(&self) -> &ConnectParams {
+        &self.params
+    }
+
+    pub fn am_channel(&self) -> Arc<Mutex<AsyncChannel>> {
+        Arc::clone(&self.am_channel)
+    }
+}
+
+// Opens a non-blocking tcp connection to the database, directly or through the configured
+// proxy, with TCP keepalive if requested.
+pub(crate) async fn connect_tcpstream(
+    params: &ConnectParams,
+) -> std::io::Result<tokio::net::TcpStream> {
+    let mut tcpstream = match params.proxy() {
+        Some(proxy) => tokio::net::TcpStream::connect(proxy.addr()).await?,
+        None => tokio::net::TcpStream::connect(params.addr()).await?,
+    };
+    super::set_tcp_keepalive(&socket2::SockRef::from(&tcpstream), params)?;
+    if let Some(proxy) = params.proxy() {
+        super::proxy::handshake_async(&mut tcpstream, proxy, params.addr()).await?;
+    }
+    Ok(tcpstream)
+}
+
+// The non-blocking stream, together with the timeouts that apply to it.
+#[derive(Debug)]
+pub(crate) struct AsyncChannel {
+    stream: AsyncStream,
+    read_timeout: Option<Duration>,
+    write_timeout: Option<Duration>,
+    // set when a roundtrip was abandoned before its reply was read completely
+    is_out_of_sync: bool,
+}
+impl AsyncChannel {
+    pub async fn write_all(&mut self, buf: &[u8]) -> std::io::Result<()> {
+        self.check_in_sync()?;
+        let write_timeout = self.write_timeout;
+        with_timeout(write_timeout, self.stream.write_all(buf)).await
+    }
+
+    pub async fn read_exact(&mut self, buf: &mut [u8]) -> std::io::Result<()> {
+        self.check_in_sync()?;
+        let read_timeout = self.read_timeout;
+        with_timeout(read_timeout, self.stream.read_exact(buf)).await
+    }
+
+    // Reads a complete message; the message header tells how much needs to be read.
+    pub async fn read_message(&mut self) -> std::io::Result<Vec<u8>> {
+        self.check_in_sync()?;
+        let read_timeout = self.read_timeout;
+        with_timeout(read_timeout, self.stream.read_message()).await
+    }
+
+    // After an abandoned roundtrip, what is read from the stream cannot be attributed
+    // to the request it belongs to anymore.
+    pub fn set_out_of_sync(&mut self) {
+        self.is_out_of_sync = true;
+    }
+
+    // A channel that is out of sync is reported like a reset connection,
+    // so that the connection is re-established.
+    fn check_in_sync(&self) -> std::io::Result<()> {
+        if self.is_out_of_sync {
+            Err(std::io::Error::new(
+                std::io::ErrorKind::ConnectionReset,
+                "an earlier roundtrip was abandoned before its reply was read",
+            ))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+#[derive(Debug)]
+enum AsyncStream {
+    Plain(PlainAsyncTcpClient),
+    Tls(Box<TlsAsyncTcpClient>),
+}
+impl AsyncStream {
+    async fn write_all(&mut self, buf: &[u8]) -> std::io::Result<()> {
+        match self {
+            Self::Plain(pc) => pc.write_all(buf).await,
+            Self::Tls(tc) => tc.write_all(buf).await,
+        }
+    }
+
+    async fn read_exact(&mut self, buf: &mut [u8]) -> std::io::Result<()> {
+        match self {
+            Self::Plain(pc) => pc.read_exact(buf).await,
+            Self::Tls(tc) => tc.read_exact(buf).await,
+        }
+    }
+
+    async fn read_message(&mut self) -> std::io::Result<Vec<u8>> {
+        let header_size = MESSAGE_HEADER_SIZE as usize;
+        let mut buf = vec![0_u8; header_size];
+        self.read_exact(&mut buf).await?;
+        let varpart_size = LittleEndian::read_u32(&buf[12..16]) as usize;
+        buf.resize(header_size + varpart_size, 0);
+        self.read_exact(&mut buf[header_size..]).await?;
+        Ok(buf)
+    }
+}
+
+// Lets the io operation fail with ErrorKind::TimedOut if it does not finish in time.
+async fn with_timeout<T, F>(o_timeout: Option<Duration>, f: F) -> std::io::Result<T>
+where
+    F: Future<Output = std::io::Result<T>>,
+{
+    match o_timeout {
+        Some(timeout) => tokio::time::timeout(timeout, f).await.map_err(|_| {
+            std::io::Error::new(
+                std::io::ErrorKind::TimedOut,
+                format!("no completion within {:?}", timeout),
+            )
+        })?,
+        None => f.await,
+    }
+}
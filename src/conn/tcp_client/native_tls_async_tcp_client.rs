@@ -0,0 +1,47 @@
+use crate::protocol::util;
+use crate::ConnectParams;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
+use tokio_native_tls::{TlsConnector, TlsStream};
+
+// Replaces the rustls based TlsAsyncTcpClient if the feature `native-tls` is active.
+pub(crate) struct TlsAsyncTcpClient {
+    tls_stream: TlsStream<TcpStream>,
+}
+impl std::fmt::Debug for TlsAsyncTcpClient {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> Result<(), std::fmt::Error> {
+        write!(f, "TlsAsyncTcpClient {{ ... }}")
+    }
+}
+impl TlsAsyncTcpClient {
+    pub async fn try_new(params: &ConnectParams) -> std::io::Result<Self> {
+        debug!("Connecting to {:?}", params.addr());
+        let tcpstream = super::async_tcp_client::connect_tcpstream(params).await?;
+        trace!("tcpstream working");
+
+        let tls_stream = TlsConnector::from(params.native_tls_connector()?)
+            .connect(params.tls_server_name(), tcpstream)
+            .await
+            .map_err(util::io_error)?;
+        let o_peer_cert = tls_stream
+            .get_ref()
+            .peer_certificate()
+            .map_err(util::io_error)?
+            .map(|cert| cert.to_der())
+            .transpose()
+            .map_err(util::io_error)?;
+        params.check_peer_certificate(o_peer_cert.as_deref())?;
+        debug!("TLS handshake with {} done", params.host());
+
+        Ok(Self { tls_stream })
+    }
+
+    pub async fn write_all(&mut self, buf: &[u8]) -> std::io::Result<()> {
+        self.tls_stream.write_all(buf).await?;
+        self.tls_stream.flush().await
+    }
+
+    pub async fn read_exact(&mut self, buf: &mut [u8]) -> std::io::Result<()> {
+        self.tls_stream.read_exact(buf).await.map(|_| ())
+    }
+}
@@ -0,0 +1,99 @@
+use super::SharedTransport;
+use crate::protocol::util;
+use crate::ConnectParams;
+use native_tls::{HandshakeError, TlsStream};
+use std::sync::{Arc, Mutex};
+
+// Replaces the rustls based TlsSyncTcpClient if the feature `native-tls` is active.
+pub(crate) struct TlsSyncTcpClient {
+    params: ConnectParams,
+    reader: Stream,
+    writer: std::io::BufWriter<Stream>,
+}
+impl std::fmt::Debug for TlsSyncTcpClient {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> Result<(), std::fmt::Error> {
+        write!(f, "TlsTcpClient {{params: {:?}, ... }}", &self.params)
+    }
+}
+impl TlsSyncTcpClient {
+    pub fn try_new(params: ConnectParams) -> std::io::Result<Self> {
+        let stream = Stream::try_new(&params)?;
+        Ok(Self {
+            params,
+            reader: stream.share(),
+            writer: std::io::BufWriter::new(stream),
+        })
+    }
+
+    pub fn connect_params(&self) -> &ConnectParams {
+        &self.params
+    }
+
+    pub(crate) fn writer(&mut self) -> &mut dyn std::io::Write {
+        &mut self.writer
+    }
+
+    pub(crate) fn reader(&mut self) -> &mut dyn std::io::Read {
+        &mut self.reader
+    }
+}
+
+struct Stream {
+    am_tls_stream: Arc<Mutex<TlsStream<SharedTransport>>>,
+}
+impl Stream {
+    pub fn try_new(params: &ConnectParams) -> std::io::Result<Self> {
+        debug!("Connecting to {:?}", params.addr());
+        let transport = super::open_transport(params)?;
+        trace!("transport working");
+
+        let tls_stream = params
+            .native_tls_connector()?
+            .connect(params.tls_server_name(), transport)
+            .map_err(|e| match e {
+                HandshakeError::Failure(e) => util::io_error(e),
+                HandshakeError::WouldBlock(_) => util::io_error("TLS handshake was interrupted"),
+            })?;
+        let o_peer_cert = tls_stream
+            .peer_certificate()
+            .map_err(util::io_error)?
+            .map(|cert| cert.to_der())
+            .transpose()
+            .map_err(util::io_error)?;
+        params.check_peer_certificate(o_peer_cert.as_deref())?;
+        debug!("TLS handshake with {} done", params.host());
+
+        Ok(Self {
+            am_tls_stream: Arc::new(Mutex::new(tls_stream)),
+        })
+    }
+
+    // Returns a second handle on the same TLS stream.
+    pub fn share(&self) -> Self {
+        Self {
+            am_tls_stream: Arc::clone(&self.am_tls_stream),
+        }
+    }
+}
+
+impl std::io::Write for Stream {
+    fn write(&mut self, request: &[u8]) -> std::io::Result<usize> {
+        trace!(
+            "std::io::Write::write() with request size {}",
+            request.len()
+        );
+        self.am_tls_stream.lock().unwrap().write(request)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        trace!("std::io::Write::flush()");
+        self.am_tls_stream.lock().unwrap().flush()
+    }
+}
+
+impl std::io::Read for Stream {
+    fn read(&mut self, buffer: &mut [u8]) -> std::io::Result<usize> {
+        trace!("std::io::Read::read() with buf size {}", buffer.len());
+        self.am_tls_stream.lock().unwrap().read(buffer)
+    }
+}
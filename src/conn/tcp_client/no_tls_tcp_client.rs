@@ -0,0 +1,50 @@
+use crate::ConnectParams;
+
+// Replaces the TLS clients if neither the feature `rustls` nor the feature `native-tls`
+// is active; connections with TLS are then rejected when they are opened.
+#[derive(Debug)]
+pub(crate) enum TlsSyncTcpClient {}
+impl TlsSyncTcpClient {
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn try_new(_params: ConnectParams) -> std::io::Result<Self> {
+        Err(tls_not_available())
+    }
+
+    pub fn connect_params(&self) -> &ConnectParams {
+        match *self {}
+    }
+
+    pub(crate) fn writer(&mut self) -> &mut dyn std::io::Write {
+        match *self {}
+    }
+
+    pub(crate) fn reader(&mut self) -> &mut dyn std::io::Read {
+        match *self {}
+    }
+}
+
+#[cfg(feature = "async")]
+#[derive(Debug)]
+pub(crate) enum TlsAsyncTcpClient {}
+#[cfg(feature = "async")]
+#[allow(clippy::unused_async)]
+impl TlsAsyncTcpClient {
+    pub async fn try_new(_params: &ConnectParams) -> std::io::Result<Self> {
+        Err(tls_not_available())
+    }
+
+    pub async fn write_all(&mut self, _buf: &[u8]) -> std::io::Result<()> {
+        match *self {}
+    }
+
+    pub async fn read_exact(&mut self, _buf: &mut [u8]) -> std::io::Result<()> {
+        match *self {}
+    }
+}
+
+fn tls_not_available() -> std::io::Error {
+    std::io::Error::new(
+        std::io::ErrorKind::InvalidInput,
+        "TLS is not available, it requires the feature rustls or native-tls",
+    )
+}
@@ -1,37 +1,28 @@
 use crate::ConnectParams;
-use std::cell::RefCell;
+use tokio::io::{AsyncReadExt, AsyncWriteExt, BufReader};
 use tokio::net::TcpStream;
 
 #[derive(Debug)]
-pub struct AsyncPlainConnection {
-    params: ConnectParams,
-    reader: RefCell<tokio::io::BufReader<TcpStream>>,
-    writer: RefCell<tokio::io::BufWriter<TcpStream>>,
+pub(crate) struct PlainAsyncTcpClient {
+    stream: BufReader<TcpStream>,
 }
 
-impl AsyncPlainConnection {
+impl PlainAsyncTcpClient {
     /// Returns an initialized plain tcp connection
-    pub async fn try_new(params: ConnectParams) -> std::io::Result<Self> {
-        let std_tcpstream = std::net::TcpStream::connect(params.addr())?;
-
-        let tcpstream_w = TcpStream::from_std(std_tcpstream.try_clone()?)?;
-        let tcpstream_r = TcpStream::from_std(std_tcpstream)?;
+    pub async fn try_new(params: &ConnectParams) -> std::io::Result<Self> {
+        let tcpstream = super::async_tcp_client::connect_tcpstream(params).await?;
         Ok(Self {
-            params,
-            writer: RefCell::new(tokio::io::BufWriter::new(tcpstream_w)),
-            reader: RefCell::new(tokio::io::BufReader::new(tcpstream_r)),
+            stream: BufReader::new(tcpstream),
         })
     }
 
-    pub fn connect_params(&self) -> &ConnectParams {
-        &self.params
-    }
-
-    pub fn writer(&self) -> &RefCell<tokio::io::BufWriter<TcpStream>> {
-        &self.writer
+    pub async fn write_all(&mut self, buf: &[u8]) -> std::io::Result<()> {
+        let tcpstream = self.stream.get_mut();
+        tcpstream.write_all(buf).await?;
+        tcpstream.flush().await
     }
 
-    pub fn reader(&self) -> &RefCell<tokio::io::BufReader<TcpStream>> {
-        &self.reader
+    pub async fn read_exact(&mut self, buf: &mut [u8]) -> std::io::Result<()> {
+        self.stream.read_exact(buf).await.map(|_| ())
     }
 }
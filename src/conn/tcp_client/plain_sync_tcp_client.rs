@@ -1,21 +1,21 @@
+use super::SharedTransport;
 use crate::ConnectParams;
-use std::net::TcpStream;
 
 #[derive(Debug)]
 pub struct PlainSyncTcpClient {
     params: ConnectParams,
-    reader: std::io::BufReader<TcpStream>,
-    writer: std::io::BufWriter<TcpStream>,
+    reader: std::io::BufReader<SharedTransport>,
+    writer: std::io::BufWriter<SharedTransport>,
 }
 
 impl PlainSyncTcpClient {
-    /// Returns an initialized plain tcp connection
+    /// Returns an initialized plain connection
     pub fn try_new(params: ConnectParams) -> std::io::Result<Self> {
-        let tcpstream = TcpStream::connect(params.addr())?;
+        let transport = super::open_transport(&params)?;
         Ok(Self {
             params,
-            writer: std::io::BufWriter::new(tcpstream.try_clone()?),
-            reader: std::io::BufReader::new(tcpstream),
+            writer: std::io::BufWriter::new(transport.clone()),
+            reader: std::io::BufReader::new(transport),
         })
     }
 
@@ -23,11 +23,11 @@ impl PlainSyncTcpClient {
         &self.params
     }
 
-    pub fn writer(&mut self) -> &mut std::io::BufWriter<TcpStream> {
+    pub fn writer(&mut self) -> &mut std::io::BufWriter<SharedTransport> {
         &mut self.writer
     }
 
-    pub fn reader(&mut self) -> &mut std::io::BufReader<TcpStream> {
+    pub fn reader(&mut self) -> &mut std::io::BufReader<SharedTransport> {
         &mut self.reader
     }
 }
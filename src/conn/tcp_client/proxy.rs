@@ -0,0 +1,464 @@
+// The handshakes with SOCKS5 and HTTP proxies, which let the proxy open the
+// connection to the database.
+//
+// The messages are built and evaluated here, the io is done by the blocking and
+// the non-blocking variant of the handshake, respectively.
+use crate::conn::ProxyParams;
+use crate::ProxyType;
+use std::io::{ErrorKind, Read, Write};
+use std::net::IpAddr;
+
+const SOCKS_VERSION: u8 = 5;
+const SOCKS_AUTH_NONE: u8 = 0;
+const SOCKS_AUTH_PASSWORD: u8 = 2;
+const SOCKS_AUTH_NOT_ACCEPTABLE: u8 = 0xFF;
+const SOCKS_PASSWORD_AUTH_VERSION: u8 = 1;
+const SOCKS_CMD_CONNECT: u8 = 1;
+const SOCKS_ATYP_IPV4: u8 = 1;
+const SOCKS_ATYP_DOMAIN: u8 = 3;
+const SOCKS_ATYP_IPV6: u8 = 4;
+
+// Lets the proxy connect to the target address (host:port), over a blocking stream.
+pub(crate) fn handshake<S: Read + Write>(
+    stream: &mut S,
+    proxy: &ProxyParams,
+    target: &str,
+) -> std::io::Result<()> {
+    debug!(
+        "Connecting to {} through {} proxy {}",
+        target,
+        proxy.proxy_type(),
+        proxy.addr()
+    );
+    match proxy.proxy_type() {
+        ProxyType::Socks5 => {
+            stream.write_all(&socks_greeting(proxy))?;
+            let mut method = [0_u8; 2];
+            stream.read_exact(&mut method)?;
+            if evaluate_socks_method(method)? {
+                stream.write_all(&socks_password_auth(proxy)?)?;
+                let mut status = [0_u8; 2];
+                stream.read_exact(&mut status)?;
+                evaluate_socks_password_auth(status)?;
+            }
+            stream.write_all(&socks_connect(target)?)?;
+            let mut reply_head = [0_u8; 5];
+            stream.read_exact(&mut reply_head)?;
+            let mut reply_tail = vec![0_u8; evaluate_socks_connect(reply_head)?];
+            stream.read_exact(&mut reply_tail)
+        }
+        ProxyType::Http => {
+            stream.write_all(http_connect(proxy, target).as_bytes())?;
+            let mut response = Vec::<u8>::with_capacity(100);
+            let mut byte = [0_u8; 1];
+            // read byte-wise, to not consume anything beyond the response header
+            while !response.ends_with(b"\r\n\r\n") {
+                stream.read_exact(&mut byte)?;
+                response.push(byte[0]);
+                check_http_response_size(&response)?;
+            }
+            evaluate_http_response(&response)
+        }
+    }
+}
+
+// Lets the proxy connect to the target address (host:port), over a non-blocking stream.
+#[cfg(feature = "async")]
+pub(crate) async fn handshake_async(
+    stream: &mut tokio::net::TcpStream,
+    proxy: &ProxyParams,
+    target: &str,
+) -> std::io::Result<()> {
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    debug!(
+        "Connecting asynchronously to {} through {} proxy {}",
+        target,
+        proxy.proxy_type(),
+        proxy.addr()
+    );
+    match proxy.proxy_type() {
+        ProxyType::Socks5 => {
+            stream.write_all(&socks_greeting(proxy)).await?;
+            let mut method = [0_u8; 2];
+            stream.read_exact(&mut method).await?;
+            if evaluate_socks_method(method)? {
+                stream.write_all(&socks_password_auth(proxy)?).await?;
+                let mut status = [0_u8; 2];
+                stream.read_exact(&mut status).await?;
+                evaluate_socks_password_auth(status)?;
+            }
+            stream.write_all(&socks_connect(target)?).await?;
+            let mut reply_head = [0_u8; 5];
+            stream.read_exact(&mut reply_head).await?;
+            let mut reply_tail = vec![0_u8; evaluate_socks_connect(reply_head)?];
+            stream.read_exact(&mut reply_tail).await.map(|_| ())
+        }
+        ProxyType::Http => {
+            stream
+                .write_all(http_connect(proxy, target).as_bytes())
+                .await?;
+            let mut response = Vec::<u8>::with_capacity(100);
+            // read byte-wise, to not consume anything beyond the response header
+            while !response.ends_with(b"\r\n\r\n") {
+                response.push(stream.read_u8().await?);
+                check_http_response_size(&response)?;
+            }
+            evaluate_http_response(&response)
+        }
+    }
+}
+
+fn socks_greeting(proxy: &ProxyParams) -> Vec<u8> {
+    if proxy.user().is_some() {
+        vec![SOCKS_VERSION, 2, SOCKS_AUTH_NONE, SOCKS_AUTH_PASSWORD]
+    } else {
+        vec![SOCKS_VERSION, 1, SOCKS_AUTH_NONE]
+    }
+}
+
+// Returns true if the proxy requires username/password authentication.
+fn evaluate_socks_method(reply: [u8; 2]) -> std::io::Result<bool> {
+    match reply {
+        [SOCKS_VERSION, SOCKS_AUTH_NONE] => Ok(false),
+        [SOCKS_VERSION, SOCKS_AUTH_PASSWORD] => Ok(true),
+        [SOCKS_VERSION, SOCKS_AUTH_NOT_ACCEPTABLE] => Err(proxy_error(
+            ErrorKind::PermissionDenied,
+            "SOCKS5 proxy accepts none of the offered authentication methods",
+        )),
+        _ => Err(proxy_error(
+            ErrorKind::InvalidData,
+            "unexpected reply from SOCKS5 proxy",
+        )),
+    }
+}
+
+#[allow(clippy::cast_possible_truncation)]
+fn socks_password_auth(proxy: &ProxyParams) -> std::io::Result<Vec<u8>> {
+    let user = proxy.user().unwrap_or_default().as_bytes();
+    let password = proxy.password().map_or(&[][..], |pw| pw.unsecure());
+    if user.len() > 255 || password.len() > 255 {
+        return Err(proxy_error(
+            ErrorKind::InvalidInput,
+            "SOCKS5 proxy user and password must not be longer than 255 bytes",
+        ));
+    }
+    let mut msg = Vec::<u8>::with_capacity(3 + user.len() + password.len());
+    msg.push(SOCKS_PASSWORD_AUTH_VERSION);
+    msg.push(user.len() as u8);
+    msg.extend_from_slice(user);
+    msg.push(password.len() as u8);
+    msg.extend_from_slice(password);
+    Ok(msg)
+}
+
+fn evaluate_socks_password_auth(reply: [u8; 2]) -> std::io::Result<()> {
+    match reply {
+        [SOCKS_PASSWORD_AUTH_VERSION, 0] => Ok(()),
+        _ => Err(proxy_error(
+            ErrorKind::PermissionDenied,
+            "authentication at SOCKS5 proxy failed",
+        )),
+    }
+}
+
+#[allow(clippy::cast_possible_truncation)]
+fn socks_connect(target: &str) -> std::io::Result<Vec<u8>> {
+    let (host, port) = split_target(target)?;
+    let mut msg = vec![SOCKS_VERSION, SOCKS_CMD_CONNECT, 0];
+    match host.parse::<IpAddr>() {
+        Ok(IpAddr::V4(ip)) => {
+            msg.push(SOCKS_ATYP_IPV4);
+            msg.extend_from_slice(&ip.octets());
+        }
+        Ok(IpAddr::V6(ip)) => {
+            msg.push(SOCKS_ATYP_IPV6);
+            msg.extend_from_slice(&ip.octets());
+        }
+        Err(_) => {
+            // the proxy resolves the hostname
+            if host.len() > 255 {
+                return Err(proxy_error(
+                    ErrorKind::InvalidInput,
+                    "hostname is too long for SOCKS5",
+                ));
+            }
+            msg.push(SOCKS_ATYP_DOMAIN);
+            msg.push(host.len() as u8);
+            msg.extend_from_slice(host.as_bytes());
+        }
+    }
+    msg.extend_from_slice(&port.to_be_bytes());
+    Ok(msg)
+}
+
+// Evaluates the first five bytes of the reply to the connect request,
+// and returns the number of bytes that still need to be read.
+fn evaluate_socks_connect(reply_head: [u8; 5]) -> std::io::Result<usize> {
+    if reply_head[0] != SOCKS_VERSION {
+        return Err(proxy_error(
+            ErrorKind::InvalidData,
+            "unexpected reply from SOCKS5 proxy",
+        ));
+    }
+    if reply_head[1] != 0 {
+        return Err(proxy_error(
+            socks_reply_kind(reply_head[1]),
+            format!(
+                "SOCKS5 proxy could not connect to the database: {}",
+                socks_reply_text(reply_head[1])
+            ),
+        ));
+    }
+    // the bound address is not needed, but must be consumed; port: 2 bytes
+    match reply_head[3] {
+        SOCKS_ATYP_IPV4 => Ok(4 - 1 + 2),
+        SOCKS_ATYP_IPV6 => Ok(16 - 1 + 2),
+        SOCKS_ATYP_DOMAIN => Ok(reply_head[4] as usize + 2),
+        _ => Err(proxy_error(
+            ErrorKind::InvalidData,
+            "unexpected address type from SOCKS5 proxy",
+        )),
+    }
+}
+
+// Only if the proxy could not reach the database, the attempt to connect can be repeated.
+fn socks_reply_kind(code: u8) -> ErrorKind {
+    match code {
+        3..=6 => ErrorKind::ConnectionRefused,
+        2 => ErrorKind::PermissionDenied,
+        _ => ErrorKind::Other,
+    }
+}
+
+fn socks_reply_text(code: u8) -> &'static str {
+    match code {
+        1 => "general failure",
+        2 => "connection not allowed by ruleset",
+        3 => "network unreachable",
+        4 => "host unreachable",
+        5 => "connection refused",
+        6 => "TTL expired",
+        7 => "command not supported",
+        8 => "address type not supported",
+        _ => "unknown error",
+    }
+}
+
+fn http_connect(proxy: &ProxyParams, target: &str) -> String {
+    let mut request = format!("CONNECT {0} HTTP/1.1\r\nHost: {0}\r\n", target);
+    if let Some(user) = proxy.user() {
+        let mut credentials = user.as_bytes().to_vec();
+        credentials.push(b':');
+        if let Some(password) = proxy.password() {
+            credentials.extend_from_slice(password.unsecure());
+        }
+        request.push_str("Proxy-Authorization: Basic ");
+        request.push_str(&base64::encode(&credentials));
+        request.push_str("\r\n");
+    }
+    request.push_str("\r\n");
+    request
+}
+
+fn check_http_response_size(response: &[u8]) -> std::io::Result<()> {
+    if response.len() > 8192 {
+        Err(proxy_error(
+            ErrorKind::InvalidData,
+            "response header from HTTP proxy is too long",
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+// Accepts any 2xx status.
+fn evaluate_http_response(response: &[u8]) -> std::io::Result<()> {
+    let response = String::from_utf8_lossy(response);
+    let status_line = response.lines().next().unwrap_or_default();
+    let mut tokens = status_line.split_whitespace();
+    match (tokens.next(), tokens.next()) {
+        (Some(version), Some(status)) if version.starts_with("HTTP/") => {
+            if status.starts_with('2') {
+                Ok(())
+            } else {
+                Err(proxy_error(
+                    http_status_kind(status),
+                    format!(
+                        "HTTP proxy could not connect to the database: {}",
+                        status_line
+                    ),
+                ))
+            }
+        }
+        _ => Err(proxy_error(
+            ErrorKind::InvalidData,
+            "unexpected response from HTTP proxy",
+        )),
+    }
+}
+
+// Only if the proxy could not reach the database (bad gateway, service unavailable,
+// gateway timeout), the attempt to connect can be repeated.
+fn http_status_kind(status: &str) -> ErrorKind {
+    match status {
+        "502" | "503" | "504" => ErrorKind::ConnectionRefused,
+        "401" | "403" | "407" => ErrorKind::PermissionDenied,
+        _ => ErrorKind::Other,
+    }
+}
+
+fn split_target(target: &str) -> std::io::Result<(&str, u16)> {
+    let pos = target
+        .rfind(':')
+        .ok_or_else(|| proxy_error(ErrorKind::InvalidInput, "database address has no port"))?;
+    let port = target[pos + 1..].parse::<u16>().map_err(|_| {
+        proxy_error(
+            ErrorKind::InvalidInput,
+            "database address has no valid port",
+        )
+    })?;
+    // IPv6 addresses are enclosed in brackets, like in "[::1]:30015"
+    let host = &target[..pos];
+    let host = host
+        .strip_prefix('[')
+        .and_then(|host| host.strip_suffix(']'))
+        .unwrap_or(host);
+    Ok((host, port))
+}
+
+fn proxy_error<E>(kind: ErrorKind, error: E) -> std::io::Error
+where
+    E: Into<Box<dyn std::error::Error + Send + Sync>>,
+{
+    std::io::Error::new(kind, error)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{handshake, split_target};
+    use crate::conn::ProxyParams;
+    use crate::ProxyType;
+    use secstr::SecStr;
+    use std::io::{Cursor, ErrorKind, Read, Write};
+
+    // Replays the proxy's replies and records what the client sends.
+    struct Replay {
+        replies: Cursor<Vec<u8>>,
+        sent: Vec<u8>,
+    }
+    impl Read for Replay {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            self.replies.read(buf)
+        }
+    }
+    impl Write for Replay {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.sent.write(buf)
+        }
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+    fn replay(replies: &[u8]) -> Replay {
+        Replay {
+            replies: Cursor::new(replies.to_vec()),
+            sent: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_socks5() {
+        let proxy = ProxyParams::new(
+            ProxyType::Socks5,
+            "proxy".to_string(),
+            1080,
+            Some("me".to_string()),
+            Some(SecStr::from("pw")),
+        );
+        let mut stream = replay(&[5, 2, 1, 0, 5, 0, 0, 1, 10, 0, 0, 1, 0x08, 0xAE]);
+        handshake(&mut stream, &proxy, "abcd123:2222").unwrap();
+        assert_eq!(
+            stream.sent,
+            [
+                &[5, 2, 0, 2][..],
+                &[1, 2, b'm', b'e', 2, b'p', b'w'][..],
+                &[5, 1, 0, 3, 7][..],
+                &b"abcd123"[..],
+                &[0x08, 0xAE][..]
+            ]
+            .concat()
+        );
+        // all replies are consumed
+        assert_eq!(stream.replies.position(), 14);
+
+        let mut stream = replay(&[5, 0, 5, 5, 0, 1, 0, 0, 0, 0, 0, 0]);
+        let err = handshake(&mut stream, &proxy, "10.0.0.1:30015").unwrap_err();
+        assert!(err.to_string().contains("connection refused"));
+    }
+
+    #[test]
+    fn test_socks5_ipv6() {
+        let proxy = ProxyParams::new(ProxyType::Socks5, "proxy".to_string(), 1080, None, None);
+        let mut stream = replay(&[5, 0, 5, 0, 0, 1, 10, 0, 0, 1, 0x08, 0xAE]);
+        handshake(&mut stream, &proxy, "[::1]:2222").unwrap();
+        let mut expected = vec![5, 1, 0, 5, 1, 0, 4];
+        expected.extend_from_slice(&[0; 15]);
+        expected.extend_from_slice(&[1, 0x08, 0xAE]);
+        assert_eq!(stream.sent, expected);
+    }
+
+    #[test]
+    fn test_split_target() {
+        assert_eq!(("abcd123", 2222), split_target("abcd123:2222").unwrap());
+        assert_eq!(("10.0.0.1", 30015), split_target("10.0.0.1:30015").unwrap());
+        assert_eq!(("::1", 30015), split_target("[::1]:30015").unwrap());
+        assert_eq!(("fe80::1:2", 443), split_target("[fe80::1:2]:443").unwrap());
+        assert!(split_target("abcd123").is_err());
+        assert!(split_target("[::1]").is_err());
+    }
+
+    #[test]
+    fn test_http() {
+        let proxy = ProxyParams::new(
+            ProxyType::Http,
+            "proxy".to_string(),
+            8080,
+            Some("me".to_string()),
+            Some(SecStr::from("pw")),
+        );
+        let mut stream = replay(b"HTTP/1.1 200 Connection established\r\n\r\nHANA");
+        handshake(&mut stream, &proxy, "abcd123:2222").unwrap();
+        assert_eq!(
+            String::from_utf8(stream.sent).unwrap(),
+            "CONNECT abcd123:2222 HTTP/1.1\r\nHost: abcd123:2222\r\n\
+             Proxy-Authorization: Basic bWU6cHc=\r\n\r\n"
+        );
+        let mut rest = String::new();
+        stream.replies.read_to_string(&mut rest).unwrap();
+        assert_eq!(rest, "HANA");
+
+        let mut stream = replay(b"HTTP/1.1 407 Proxy Authentication Required\r\n\r\n");
+        assert_eq!(
+            handshake(&mut stream, &proxy, "abcd123:2222")
+                .unwrap_err()
+                .kind(),
+            ErrorKind::PermissionDenied
+        );
+
+        let mut stream = replay(b"HTTP/1.1 503 Service Unavailable\r\n\r\n");
+        assert_eq!(
+            handshake(&mut stream, &proxy, "abcd123:2222")
+                .unwrap_err()
+                .kind(),
+            ErrorKind::ConnectionRefused
+        );
+
+        let mut stream = replay(b"SSH-2.0-OpenSSH\r\n\r\n");
+        assert_eq!(
+            handshake(&mut stream, &proxy, "abcd123:2222")
+                .unwrap_err()
+                .kind(),
+            ErrorKind::InvalidData
+        );
+    }
+}
@@ -0,0 +1,97 @@
+use crate::protocol::util;
+use crate::ConnectParams;
+use rustls::{ClientSession, Session};
+use std::io::{Read, Write};
+use std::sync::Arc;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
+use webpki::DNSNameRef;
+
+const TLS_READ_BUFFER_SIZE: usize = 16_384;
+
+// rustls works without own IO, so we shovel the TLS records between the
+// client session and the non-blocking tcp stream ourselves.
+pub(crate) struct TlsAsyncTcpClient {
+    tcpstream: TcpStream,
+    client_session: ClientSession,
+}
+impl std::fmt::Debug for TlsAsyncTcpClient {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> Result<(), std::fmt::Error> {
+        write!(f, "TlsAsyncTcpClient {{ ... }}")
+    }
+}
+impl TlsAsyncTcpClient {
+    pub async fn try_new(params: &ConnectParams) -> std::io::Result<Self> {
+        debug!("Connecting to {:?}", params.addr());
+        let tcpstream = super::async_tcp_client::connect_tcpstream(params).await?;
+        trace!("tcpstream working");
+
+        let client_session = ClientSession::new(
+            &Arc::new(params.rustls_clientconfig()?),
+            DNSNameRef::try_from_ascii_str(params.tls_server_name()).map_err(|_| {
+                util::io_error(format!(
+                    "Cannot use {} for DNSNameRef",
+                    params.tls_server_name()
+                ))
+            })?,
+        );
+
+        Ok(Self {
+            tcpstream,
+            client_session,
+        })
+    }
+
+    pub async fn write_all(&mut self, buf: &[u8]) -> std::io::Result<()> {
+        self.client_session.write_all(buf)?;
+        loop {
+            while self.client_session.wants_write() {
+                let mut tls_data = Vec::<u8>::with_capacity(buf.len() + 100);
+                let count = self.client_session.write_tls(&mut tls_data)?;
+                trace!("write_all(): wrote tls ({})", count);
+                self.tcpstream.write_all(&tls_data).await?;
+            }
+            if self.client_session.is_handshaking() {
+                self.read_tls().await?;
+            } else {
+                break;
+            }
+        }
+        self.tcpstream.flush().await
+    }
+
+    pub async fn read_exact(&mut self, buf: &mut [u8]) -> std::io::Result<()> {
+        let mut pos = 0;
+        while pos < buf.len() {
+            let count = self.client_session.read(&mut buf[pos..])?;
+            if count == 0 {
+                self.read_tls().await?;
+            } else {
+                pos += count;
+            }
+        }
+        Ok(())
+    }
+
+    // reads whatever is available from the tcp stream and lets the client session
+    // digest it
+    async fn read_tls(&mut self) -> std::io::Result<()> {
+        let mut tls_data = vec![0_u8; TLS_READ_BUFFER_SIZE];
+        let count = self.tcpstream.read(&mut tls_data).await?;
+        trace!("read_tls(): read {} bytes from tcpstream", count);
+        if count == 0 {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::UnexpectedEof,
+                "connection closed by the server",
+            ));
+        }
+        let mut rdr: &[u8] = &tls_data[0..count];
+        while !rdr.is_empty() {
+            self.client_session.read_tls(&mut rdr)?;
+            self.client_session
+                .process_new_packets()
+                .map_err(util::io_error)?;
+        }
+        Ok(())
+    }
+}
@@ -1,3 +1,4 @@
+use super::SharedTransport;
 use crate::protocol::util;
 use crate::ConnectParams;
 use rustls::{ClientSession, Session};
@@ -19,7 +20,7 @@ impl TlsSyncTcpClient {
         let stream = Stream::try_new(&params)?;
         Ok(Self {
             params,
-            reader: stream.try_clone()?,
+            reader: stream.share(),
             writer: std::io::BufWriter::new(stream),
         })
     }
@@ -39,35 +40,39 @@ impl TlsSyncTcpClient {
 
 struct Stream {
     is_handshaking: bool,
-    tcpstream: std::net::TcpStream,
+    transport: SharedTransport,
     am_client_session: Arc<Mutex<ClientSession>>,
 }
 impl Stream {
     pub fn try_new(params: &ConnectParams) -> std::io::Result<Self> {
         debug!("Connecting to {:?}", params.addr());
-        let tcpstream = std::net::TcpStream::connect(params.addr())?;
-        trace!("tcpstream working");
+        let transport = super::open_transport(params)?;
+        trace!("transport working");
 
         let am_client_session = Arc::new(Mutex::new(ClientSession::new(
             &Arc::new(params.rustls_clientconfig()?),
-            DNSNameRef::try_from_ascii_str(params.host()).map_err(|_| {
-                util::io_error(format!("Cannot use {} for DNSNameRef", params.host()))
+            DNSNameRef::try_from_ascii_str(params.tls_server_name()).map_err(|_| {
+                util::io_error(format!(
+                    "Cannot use {} for DNSNameRef",
+                    params.tls_server_name()
+                ))
             })?,
         )));
 
         Ok(Self {
             is_handshaking: true,
-            tcpstream,
+            transport,
             am_client_session,
         })
     }
 
-    pub fn try_clone(&self) -> std::io::Result<Self> {
-        Ok(Self {
+    // Returns a second handle on the same TLS session and transport.
+    pub fn share(&self) -> Self {
+        Self {
             is_handshaking: false,
-            tcpstream: self.tcpstream.try_clone()?,
+            transport: self.transport.clone(),
             am_client_session: Arc::clone(&self.am_client_session),
-        })
+        }
     }
 }
 
@@ -83,7 +88,7 @@ impl std::io::Write for Stream {
 
         if self.is_handshaking {
             while client_session.wants_write() {
-                let count = client_session.write_tls(&mut self.tcpstream)?;
+                let count = client_session.write_tls(&mut self.transport)?;
                 trace!("std::io::Write::write(): wrote tls ({})", count);
             }
         }
@@ -96,7 +101,7 @@ impl std::io::Write for Stream {
 
         loop {
             while client_session.wants_write() {
-                let count = client_session.write_tls(&mut self.tcpstream)?;
+                let count = client_session.write_tls(&mut self.transport)?;
                 trace!("std::io::Write::flush(): wrote tls ({})", count);
             }
 
@@ -111,7 +116,7 @@ impl std::io::Write for Stream {
             }
 
             if client_session.wants_read() {
-                let count = client_session.read_tls(&mut self.tcpstream)?;
+                let count = client_session.read_tls(&mut self.transport)?;
                 trace!("std::io::Write::flush(): read_tls() -> {}", count);
                 if count == 0 {
                     break;
@@ -135,7 +140,7 @@ impl std::io::Read for Stream {
 
         loop {
             if client_session.wants_read() {
-                let count = client_session.read_tls(&mut self.tcpstream)?;
+                let count = client_session.read_tls(&mut self.transport)?;
                 trace!("transfer_read(): read_tls() -> {}", count);
                 if count == 0 {
                     break;
@@ -0,0 +1,89 @@
+use crate::ConnectParams;
+use std::io::{Read, Write};
+use std::sync::Arc;
+
+/// A bidirectional byte stream over which the driver communicates with the database.
+///
+/// `Transport` is implemented for all types that implement `Read`, `Write`, and `Send`,
+/// like `std::net::TcpStream`.
+pub trait Transport: Read + Write + Send {}
+impl<T: Read + Write + Send> Transport for T {}
+
+/// Opens the [`Transport`](trait.Transport.html)s for new connections.
+///
+/// By default, the driver opens a tcp connection to the address in the `ConnectParams`.
+/// A `TransportFactory` can be registered with
+/// [`ConnectParamsBuilder::transport_factory`](struct.ConnectParamsBuilder.html#method.transport_factory)
+/// to let the driver communicate over other streams instead, like an in-process test double,
+/// a forwarded channel, or a socket that was established by some other component.
+///
+/// The driver calls the factory whenever it needs a new stream, i.e. when a connection is
+/// opened and when it is re-established. If TLS is configured, it is applied on top of the
+/// returned stream. The timeout and TCP keepalive settings of the `ConnectParams` are
+/// not applied; the factory is responsible for configuring the stream.
+///
+/// `TransportFactory` is implemented for suitable closures.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use hdbconnect::{ConnectParams, Connection, Transport};
+/// use std::net::TcpStream;
+///
+/// let connect_params = ConnectParams::builder()
+///     .hostname("abcd123")
+///     .port(2222)
+///     .dbuser("MEIER")
+///     .password("schlau")
+///     .transport_factory(|params: &ConnectParams| {
+///         let tcpstream = TcpStream::connect(params.addr())?;
+///         tcpstream.set_nodelay(true)?;
+///         Ok(Box::new(tcpstream) as Box<dyn Transport>)
+///     })
+///     .build()
+///     .unwrap();
+/// let connection = Connection::new(connect_params).unwrap();
+/// ```
+pub trait TransportFactory: Send + Sync {
+    /// Opens a new stream to the database that is described by the given `ConnectParams`.
+    ///
+    /// # Errors
+    ///
+    /// Errors are reported to the application as `HdbError::Tcp` or `HdbError::Timeout`.
+    fn connect(&self, params: &ConnectParams) -> std::io::Result<Box<dyn Transport>>;
+}
+impl<F> TransportFactory for F
+where
+    F: Fn(&ConnectParams) -> std::io::Result<Box<dyn Transport>> + Send + Sync,
+{
+    fn connect(&self, params: &ConnectParams) -> std::io::Result<Box<dyn Transport>> {
+        self(params)
+    }
+}
+
+// A shareable TransportFactory, which allows ConnectParams and ConnectParamsBuilder
+// to keep their derived trait implementations.
+#[derive(Clone)]
+pub(crate) struct AmTransportFactory(Arc<dyn TransportFactory>);
+impl AmTransportFactory {
+    pub fn new<F: TransportFactory + 'static>(factory: F) -> Self {
+        Self(Arc::new(factory))
+    }
+
+    pub fn connect(&self, params: &ConnectParams) -> std::io::Result<Box<dyn Transport>> {
+        self.0.connect(params)
+    }
+}
+impl std::fmt::Debug for AmTransportFactory {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "TransportFactory {{ ... }}")
+    }
+}
+impl PartialEq for AmTransportFactory {
+    fn eq(&self, other: &Self) -> bool {
+        std::ptr::eq(
+            Arc::as_ptr(&self.0).cast::<u8>(),
+            Arc::as_ptr(&other.0).cast::<u8>(),
+        )
+    }
+}
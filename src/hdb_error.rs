@@ -1,6 +1,7 @@
 #![allow(clippy::used_underscore_binding)]
 
-use crate::protocol::parts::{ExecutionResult, ServerError};
+use crate::protocol::parts::{ExecutionResult, ServerError, Severity};
+use crate::protocol::Request;
 // use std::backtrace::Backtrace;
 use thiserror::Error;
 
@@ -86,11 +87,56 @@ pub enum HdbError {
     #[error("Error occured in communication with the database")]
     Tcp {
         /// The causing Error.
-        #[from]
         source: std::io::Error,
         // backtrace: Backtrace,
     },
 
+    /// Communication with the database did not finish within one of the timeouts
+    /// that were configured in the `ConnectParams`.
+    ///
+    /// The state of the connection is undefined after a read or write timeout,
+    /// so the connection should not be used anymore.
+    #[error("Timeout occured in communication with the database")]
+    Timeout {
+        /// The causing Error.
+        source: std::io::Error,
+    },
+
+    /// The database server aborted the statement because its query timeout expired,
+    /// see e.g. [`Connection::set_query_timeout()`](struct.Connection.html#method.set_query_timeout).
+    ///
+    /// Other than after a `Timeout`, the connection can still be used.
+    #[error("Query timeout expired, the statement was aborted by the database server")]
+    QueryTimeout {
+        /// The causing Error.
+        source: ServerError,
+    },
+
+    /// The statement was aborted by the database server because it was cancelled with a
+    /// [`CancelHandle`](struct.CancelHandle.html).
+    ///
+    /// The connection can still be used.
+    #[error("The statement was cancelled")]
+    Cancelled {
+        /// The causing Error.
+        source: ServerError,
+    },
+
+    /// The connection to the database was lost while a transaction with uncommitted changes
+    /// was open, and has been re-established.
+    ///
+    /// The uncommitted changes are lost, and the failed request was not repeated.
+    /// If the connection was re-established by the heartbeat, the next request fails
+    /// with this error and is not executed.
+    /// The connection can still be used.
+    #[error("The connection was re-established, but the open transaction was lost")]
+    TransactionLost,
+
+    /// No connection became available in the
+    /// [`ConnectionPool`](struct.ConnectionPool.html) within its connection timeout.
+    #[error("Timeout while waiting for a connection from the pool")]
+    PoolTimeout,
+
     /// Error caused by wrong usage.
     #[error("Wrong usage: {}", _0)]
     Usage(&'static str),
@@ -100,6 +146,9 @@ pub enum HdbError {
     UsageDetailed(String),
 }
 
+// "current operation cancelled by request and transaction rolled back"
+const ERR_CODE_CANCELLED: i32 = 139;
+
 /// Abbreviation of `Result<T, HdbError>`.
 pub type HdbResult<T> = std::result::Result<T, HdbError>;
 
@@ -133,16 +182,58 @@ impl HdbError {
         match self {
             Self::DbError {
                 source: server_error,
+            }
+            | Self::QueryTimeout {
+                source: server_error,
+            }
+            | Self::Cancelled {
+                source: server_error,
             } => Some(&server_error),
             _ => None,
         }
     }
 
+    // The server reports an expired query timeout like a cancelled statement,
+    // so we can only tell the two apart by looking at the request
+    // and at whether a cancellation was aimed at the roundtrip.
+    pub(crate) fn classify_for(self, request: &Request, cancel_aimed_at: bool) -> Self {
+        match self {
+            Self::DbError { source } if source.code() == ERR_CODE_CANCELLED => {
+                if cancel_aimed_at {
+                    Self::Cancelled { source }
+                } else if request.query_timeout().is_some_and(|qt| !qt.is_zero()) {
+                    Self::QueryTimeout { source }
+                } else {
+                    Self::DbError { source }
+                }
+            }
+            e => e,
+        }
+    }
+
+    // Errors after which the connection cannot be used anymore.
+    pub(crate) fn is_fatal(&self) -> bool {
+        match self {
+            Self::Tcp { .. } | Self::Timeout { .. } | Self::SessionClosingTransactionError => true,
+            Self::DbError { source } => source.severity() == &Severity::Fatal,
+            _ => false,
+        }
+    }
+
     pub(crate) fn conn_params(error: Box<dyn std::error::Error + Send + Sync + 'static>) -> Self {
         Self::ConnParams { source: error }
     }
 }
 
+impl From<std::io::Error> for HdbError {
+    fn from(error: std::io::Error) -> Self {
+        match error.kind() {
+            std::io::ErrorKind::TimedOut => Self::Timeout { source: error },
+            _ => Self::Tcp { source: error },
+        }
+    }
+}
+
 impl<G> From<std::sync::PoisonError<G>> for HdbError {
     fn from(_error: std::sync::PoisonError<G>) -> Self {
         Self::Poison
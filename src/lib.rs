@@ -23,6 +23,19 @@
 //!
 //! See [code examples](code_examples/index.html) for an overview.
 //!
+//! With the feature `async`, `hdbconnect` additionally provides a tokio-based
+//! [`AsyncConnection`](struct.AsyncConnection.html).
+//!
+//! By default, TLS is implemented with `rustls` (default feature `rustls`). With the feature
+//! `native-tls`, both blocking and async connections use the platform's TLS library instead
+//! (OpenSSL on Linux, via `tokio-native-tls` for async connections), configured with the
+//! same [`ServerCerts`](enum.ServerCerts.html) and [`ClientCerts`](enum.ClientCerts.html);
+//! with `default-features = false`, `rustls` is then not even compiled in.
+//! Private keys of client certificates must then be given in PKCS#8 format.
+//! `ServerCerts::RootCertificates` and a custom `ServerCertVerifier` are not supported with
+//! `native-tls` and let the connect fail; use `ServerCerts::SystemStore` instead of the former.
+//! Without either feature, connections with TLS cannot be opened.
+//!
 
 #![deny(missing_docs)]
 #![deny(missing_debug_implementations)]
@@ -42,34 +55,51 @@ extern crate serde;
 pub use r2d2;
 pub use serde_db;
 
+#[cfg(feature = "async")]
+mod async_connection;
+#[cfg(feature = "async")]
+mod async_prepared_statement;
+#[cfg(feature = "async")]
+mod async_resultset;
 mod conn;
 mod hdb_error;
 mod hdb_response;
 mod hdb_return_value;
 mod protocol;
 mod serde_db_impl;
+mod statement_cache;
 mod sync_connection;
 mod sync_connection_manager;
+mod sync_connection_pool;
 mod sync_prepared_statement;
 mod types_impl;
 mod xa_impl;
 
 pub mod code_examples;
 
+#[cfg(feature = "async")]
+pub use crate::async_connection::AsyncConnection;
+#[cfg(feature = "async")]
+pub use crate::async_prepared_statement::AsyncPreparedStatement;
+#[cfg(feature = "async")]
+pub use crate::async_resultset::AsyncResultSet;
 pub use crate::conn::{
-    ConnectParams, ConnectParamsBuilder, IntoConnectParams, IntoConnectParamsBuilder, ServerCerts,
+    CancelHandle, ClientCerts, ConnectParams, ConnectParamsBuilder, IntoConnectParams,
+    IntoConnectParamsBuilder, ProxyType, ServerCertVerifier, ServerCerts, Transport,
+    TransportFactory,
 };
 pub use crate::hdb_error::{HdbError, HdbResult};
 pub use crate::hdb_response::HdbResponse;
 pub use crate::hdb_return_value::HdbReturnValue;
 pub use crate::protocol::parts::{
-    ExecutionResult, HdbValue, OutputParameters, ParameterBinding, ParameterDescriptor,
-    ParameterDescriptors, ParameterDirection, ResultSet, ResultSetMetadata, Row, ServerError,
-    Severity, TypeId,
+    ExecutionResult, HdbValue, HostDescriptor, OutputParameters, ParameterBinding,
+    ParameterDescriptor, ParameterDescriptors, ParameterDirection, ResultSet, ResultSetMetadata,
+    Row, ServerError, ServiceType, Severity, SiteType, TypeId,
 };
 pub use crate::protocol::ServerUsage;
 pub use crate::sync_connection::Connection;
-pub use crate::sync_connection_manager::ConnectionManager;
+pub use crate::sync_connection_manager::{ConnectionManager, ConnectionValidation};
+pub use crate::sync_connection_pool::{ConnectionPool, ConnectionPoolBuilder, PooledConnection};
 pub use crate::sync_prepared_statement::PreparedStatement;
 
 /// Non-standard types that are used within the
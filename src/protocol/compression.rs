@@ -0,0 +1,87 @@
+//! Network compression.
+//!
+//! If compression is negotiated with the `CompressionLevelAndFlags` connect option,
+//! the variable part of a message (everything after the message header) can be
+//! sent as a single LZ4 block.
+//! Such messages are flagged with `PACKET_OPTION_COMPRESSED` in the packet options
+//! of the message header, which then also carries the uncompressed length of the variable part.
+use crate::protocol::util;
+
+// Bit in the packet options of the message header that marks a compressed varpart.
+pub(crate) const PACKET_OPTION_COMPRESSED: u8 = 0b10;
+
+// The value we propose for the connect option CompressionLevelAndFlags;
+// the server confirms compression by answering with a non-zero value.
+pub(crate) const COMPRESSION_LEVEL_AND_FLAGS: i32 = 1;
+
+// Small messages are not worth the effort.
+const MIN_SIZE_FOR_COMPRESSION: usize = 10 * 1024;
+
+// The uncompressed length is announced by the server and must be checked before
+// the buffer is allocated: it cannot exceed the maximal packet size, and LZ4 cannot
+// expand a single byte of a block to more than 255 bytes.
+const MAX_UNCOMPRESSED_LENGTH: usize = i32::MAX as usize;
+const MAX_LZ4_EXPANSION: usize = 255;
+
+// Compresses the given varpart if that is likely to be beneficial,
+// and returns None otherwise.
+pub(crate) fn compress(varpart: &[u8]) -> Option<Vec<u8>> {
+    if varpart.len() < MIN_SIZE_FOR_COMPRESSION {
+        return None;
+    }
+    let compressed = lz4_flex::block::compress(varpart);
+    if compressed.len() < varpart.len() {
+        trace!(
+            "compressed varpart from {} to {} bytes",
+            varpart.len(),
+            compressed.len()
+        );
+        Some(compressed)
+    } else {
+        None
+    }
+}
+
+// Decompresses a varpart that was received with PACKET_OPTION_COMPRESSED.
+pub(crate) fn decompress(
+    compressed: &[u8],
+    uncompressed_length: usize,
+) -> std::io::Result<Vec<u8>> {
+    if uncompressed_length > MAX_UNCOMPRESSED_LENGTH
+        || uncompressed_length > compressed.len().saturating_mul(MAX_LZ4_EXPANSION)
+    {
+        return Err(util::io_error(format!(
+            "compressed reply of {} bytes announces implausible length {}",
+            compressed.len(),
+            uncompressed_length
+        )));
+    }
+    let varpart = lz4_flex::block::decompress(compressed, uncompressed_length)
+        .map_err(|e| util::io_error(format!("decompression of reply failed: {}", e)))?;
+    if varpart.len() == uncompressed_length {
+        Ok(varpart)
+    } else {
+        Err(util::io_error(format!(
+            "decompressed reply has length {}, expected was {}",
+            varpart.len(),
+            uncompressed_length
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn test_roundtrip() {
+        let small = b"too small to be compressed".to_vec();
+        assert!(super::compress(&small).is_none());
+
+        let big = "Some text that repeats itself. ".repeat(2000).into_bytes();
+        let compressed = super::compress(&big).unwrap();
+        assert!(compressed.len() < big.len());
+        assert_eq!(big, super::decompress(&compressed, big.len()).unwrap());
+        assert!(super::decompress(&compressed, big.len() - 1).is_err());
+        assert!(super::decompress(&compressed, u32::MAX as usize).is_err());
+        assert!(super::decompress(&compressed, compressed.len() * 256).is_err());
+    }
+}
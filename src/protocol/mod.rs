@@ -1,3 +1,4 @@
+mod compression;
 mod part;
 mod part_attributes;
 mod partkind;
@@ -9,6 +10,9 @@ mod request_type;
 mod server_usage;
 pub(crate) mod util;
 
+pub(crate) use self::compression::COMPRESSION_LEVEL_AND_FLAGS;
+#[cfg(feature = "async")]
+pub(crate) use self::request::MESSAGE_HEADER_SIZE;
 pub(crate) use self::{
     part::Part, part_attributes::PartAttributes, partkind::PartKind, reply::Reply,
     reply_type::ReplyType, request::Request, request::HOLD_CURSORS_OVER_COMMIT,
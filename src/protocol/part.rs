@@ -1,10 +1,10 @@
 use crate::conn::AmConnCore;
 use crate::protocol::parts::{
-    AuthFields, ClientContext, ClientInfo, CommandInfo, ConnectOptions, ExecutionResult, LobFlags,
-    OutputParameters, ParameterDescriptors, ParameterRows, PartitionInformation, Parts,
-    ReadLobReply, ReadLobRequest, ResultSet, ResultSetMetadata, RsState, ServerError,
-    SessionContext, StatementContext, Topology, TransactionFlags, WriteLobReply, WriteLobRequest,
-    XatOptions,
+    AuthFields, ClientContext, ClientInfo, CommandInfo, ConnectOptions, DbConnectInfo,
+    ExecutionResult, LobFlags, OutputParameters, ParameterDescriptors, ParameterRows,
+    PartitionInformation, Parts, ReadLobReply, ReadLobRequest, ResultSet, ResultSetMetadata,
+    RsState, ServerError, SessionContext, SessionVariables, StatementContext, Topology,
+    TransactionFlags, WriteLobReply, WriteLobRequest, XatOptions,
 };
 use crate::protocol::{util, PartAttributes, PartKind};
 use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
@@ -24,6 +24,7 @@ pub(crate) enum Part<'a> {
     CommandInfo(CommandInfo),
     // CommitOptions(super::parts::commit_options::CommitOptions), // not used by any client
     ConnectOptions(ConnectOptions),
+    DbConnectInfo(DbConnectInfo),
     Error(Vec<ServerError>),
     // FetchOptions(super::parts::fetch_options::FetchOptions),    // not used by any client
     FetchSize(u32),
@@ -40,6 +41,7 @@ pub(crate) enum Part<'a> {
     ResultSetMetadata(ResultSetMetadata),
     ExecutionResult(Vec<ExecutionResult>),
     SessionContext(SessionContext),
+    SessionVariable(SessionVariables),
     StatementContext(StatementContext),
     StatementId(u64),
     PartitionInformation(PartitionInformation),
@@ -58,6 +60,7 @@ impl<'a> Part<'a> {
             Self::Command(_) => PartKind::Command,
             Self::CommandInfo(_) => PartKind::CommandInfo,
             Self::ConnectOptions(_) => PartKind::ConnectOptions,
+            Self::DbConnectInfo(_) => PartKind::DbConnectInfo,
             Self::Error(_) => PartKind::Error,
             Self::FetchSize(_) => PartKind::FetchSize,
             Self::LobFlags(_) => PartKind::LobFlags,
@@ -73,6 +76,7 @@ impl<'a> Part<'a> {
             Self::ResultSetMetadata(_) => PartKind::ResultSetMetadata,
             Self::ExecutionResult(_) => PartKind::ExecutionResult,
             Self::SessionContext(_) => PartKind::SessionContext,
+            Self::SessionVariable(_) => PartKind::SessionVariable,
             Self::StatementContext(_) => PartKind::StatementContext,
             Self::StatementId(_) => PartKind::StatementId,
             Self::PartitionInformation(_) => PartKind::PartitionInformation,
@@ -99,10 +103,12 @@ impl<'a> Part<'a> {
             Part::CommandInfo(ref opts) => opts.len(),
             // Part::CommitOptions(ref opts) => opts.count(),
             Part::ConnectOptions(ref opts) => opts.len(),
+            Part::DbConnectInfo(ref opts) => opts.len(),
             // Part::FetchOptions(ref opts) => opts.count(),
             Part::LobFlags(ref opts) => opts.len(),
             Part::Parameters(ref par_rows) => par_rows.count(),
             Part::SessionContext(ref opts) => opts.len(),
+            Part::SessionVariable(ref session_variables) => session_variables.count(),
             Part::StatementContext(ref sc) => sc.len(),
             Part::TransactionFlags(ref opts) => opts.len(),
             Part::XatOptions(ref xat) => xat.len(),
@@ -133,6 +139,7 @@ impl<'a> Part<'a> {
             Part::CommandInfo(ref opts) => size += opts.size(),
             // Part::CommitOptions(ref opts) => size += opts.size(),
             Part::ConnectOptions(ref conn_opts) => size += conn_opts.size(),
+            Part::DbConnectInfo(ref opts) => size += opts.size(),
             // Part::FetchOptions(ref opts) => size += opts.size(),
             Part::FetchSize(_) => size += 4,
             Part::LobFlags(ref opts) => size += opts.size(),
@@ -147,6 +154,7 @@ impl<'a> Part<'a> {
             Part::WriteLobRequest(ref r) => size += r.size(),
             Part::ResultSetId(_) | Part::StatementId(_) => size += 8,
             Part::SessionContext(ref opts) => size += opts.size(),
+            Part::SessionVariable(ref session_variables) => size += session_variables.size(),
             Part::StatementContext(ref sc) => size += sc.size(),
             // Part::TopologyInformation(ref topology) => size += topology.size(),
             Part::TransactionFlags(ref taflags) => size += taflags.size(),
@@ -211,6 +219,7 @@ impl<'a> Part<'a> {
             Part::CommandInfo(ref opts) => opts.emit(w)?,
             // Part::CommitOptions(ref opts) => opts.emit(w)?,
             Part::ConnectOptions(ref conn_opts) => conn_opts.emit(w)?,
+            Part::DbConnectInfo(ref opts) => opts.emit(w)?,
 
             // Part::FetchOptions(ref opts) => opts.emit(w)?,
             Part::FetchSize(fs) => {
@@ -229,6 +238,7 @@ impl<'a> Part<'a> {
                 w.write_u64::<LittleEndian>(rs_id)?;
             }
             Part::SessionContext(ref opts) => opts.emit(w)?,
+            Part::SessionVariable(ref session_variables) => session_variables.emit(w)?,
             Part::StatementId(stmt_id) => {
                 w.write_u64::<LittleEndian>(stmt_id)?;
             }
@@ -320,6 +330,7 @@ impl<'a> Part<'a> {
             PartKind::ConnectOptions => {
                 Part::ConnectOptions(ConnectOptions::parse(no_of_args, rdr)?)
             }
+            PartKind::DbConnectInfo => Part::DbConnectInfo(DbConnectInfo::parse(no_of_args, rdr)?),
             PartKind::Error => Part::Error(ServerError::parse(no_of_args, rdr)?),
             PartKind::OutputParameters => o_a_descriptors
                 .ok_or_else(|| util::io_error("Parsing output parameters needs metadata"))
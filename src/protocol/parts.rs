@@ -4,6 +4,7 @@ mod client_info;
 mod command_info;
 mod commit_options;
 mod connect_options;
+mod db_connect_info;
 mod execution_result;
 mod fetch_options;
 mod hdb_value;
@@ -22,6 +23,7 @@ mod resultset_metadata;
 mod row;
 mod server_error;
 mod session_context;
+mod session_variables;
 mod statement_context;
 mod topology;
 mod transactionflags;
@@ -35,7 +37,8 @@ pub(crate) use self::{
     client_context::{ClientContext, ClientContextId},
     client_info::ClientInfo,
     command_info::CommandInfo,
-    connect_options::{ConnOptId, ConnectOptions},
+    connect_options::{ConnOptId, ConnectOptions, CLIENT_DISTRIBUTION_MODE_STATEMENT},
+    db_connect_info::DbConnectInfo,
     lob_flags::LobFlags,
     option_value::OptionValue,
     parameter_rows::ParameterRows,
@@ -44,6 +47,7 @@ pub(crate) use self::{
     read_lob_request::ReadLobRequest,
     resultset::{AmRsCore, RsState},
     session_context::SessionContext,
+    session_variables::SessionVariables,
     statement_context::StatementContext,
     topology::Topology,
     transactionflags::{TaFlagId, TransactionFlags},
@@ -56,7 +60,8 @@ pub use self::{
     parameter_descriptor::ParameterBinding, parameter_descriptor::ParameterDescriptor,
     parameter_descriptor::ParameterDescriptors, parameter_descriptor::ParameterDirection,
     resultset::ResultSet, resultset_metadata::ResultSetMetadata, row::Row,
-    server_error::ServerError, server_error::Severity, type_id::TypeId,
+    server_error::ServerError, server_error::Severity, topology::HostDescriptor,
+    topology::ServiceType, topology::SiteType, type_id::TypeId,
 };
 
 use super::{Part, PartKind};
@@ -45,6 +45,14 @@ impl ClientInfo {
     pub fn set_application_user(&mut self, application_user: &str) {
         self.set(ClientInfoKey::ApplicationUser, application_user);
     }
+    // Values that were set by the application are overwritten with empty strings,
+    // so that the server forgets them as well.
+    pub fn reset(&mut self) {
+        for value in self.0.values_mut() {
+            value.clear();
+        }
+        self.0.extend(Self::default().0);
+    }
     fn set_driver(&mut self, driver: &str) {
         self.set(ClientInfoKey::Driver, driver);
     }
@@ -1,13 +1,23 @@
 use crate::hdb_error::HdbResult;
 use crate::protocol::parts::option_part::{OptionId, OptionPart};
 use crate::protocol::parts::option_value::OptionValue;
+use crate::protocol::COMPRESSION_LEVEL_AND_FLAGS;
 
 // An Options part that is used for describing the connection's capabilities.
 // It is used during authentication, both in requests and replies.
 pub(crate) type ConnectOptions = OptionPart<ConnOptId>;
 
+// The mode STATEMENT of ConnOptId::ClientDistributionMode, see below.
+pub(crate) const CLIENT_DISTRIBUTION_MODE_STATEMENT: i32 = 2;
+
 impl ConnectOptions {
-    pub fn for_server(locale: Option<&str>, os_user: String) -> Self {
+    pub fn for_server(
+        locale: Option<&str>,
+        os_user: String,
+        compression: bool,
+        statement_routing: bool,
+        read_only_routing: bool,
+    ) -> Self {
         let mut connopts = Self::default();
 
         let mut set_opt = |id: ConnOptId, value: OptionValue| {
@@ -24,24 +34,49 @@ impl ConnectOptions {
         // how about e.g. TABLEOUTPUTPARAMETER and DESCRIBETABLEOUTPUTPARAMETER?
         set_opt(ConnOptId::DataFormatVersion2, OptionValue::INT(8));
         set_opt(ConnOptId::OSUser, OptionValue::STRING(os_user));
+        // statements can be aborted by the server when their query timeout expires
+        set_opt(ConnOptId::QueryTimeoutOK, OptionValue::BOOLEAN(true));
+        // topology changes are evaluated with every reply
+        set_opt(ConnOptId::UpdateTopologyAnwhere, OptionValue::BOOLEAN(true));
 
         if let Some(cl) = locale {
             set_opt(ConnOptId::ClientLocale, OptionValue::STRING(cl.to_owned()));
         }
 
-        if cfg!(feature = "alpha_routing") {
-            warn!("Feature alpha_routing is active!");
+        if compression {
+            set_opt(
+                ConnOptId::CompressionLevelAndFlags,
+                OptionValue::INT(COMPRESSION_LEVEL_AND_FLAGS),
+            );
+        }
+
+        if statement_routing {
             set_opt(ConnOptId::DistributionEnabled, OptionValue::BOOLEAN(true));
-            set_opt(ConnOptId::ClientDistributionMode, OptionValue::INT(0));
+            set_opt(
+                ConnOptId::ClientDistributionMode,
+                OptionValue::INT(CLIENT_DISTRIBUTION_MODE_STATEMENT),
+            );
             set_opt(ConnOptId::DistributionProtocolVersion, OptionValue::INT(1));
-        } else {
-            debug!("Feature alpha_routing is not active.");
+        }
+
+        if read_only_routing {
+            set_opt(ConnOptId::ActiveActiveProtocolVersion, OptionValue::INT(1));
         }
 
         connopts
     }
 
     pub(crate) fn digest_server_connect_options(&mut self, server_co: Self) -> HdbResult<()> {
+        // a server that does not answer the compression request does not support it
+        if server_co
+            .get(&ConnOptId::CompressionLevelAndFlags)
+            .is_none()
+            && self
+                .remove_entry(&ConnOptId::CompressionLevelAndFlags)
+                .is_some()
+        {
+            info!("Server does not support network compression");
+        }
         for (k, v) in server_co {
             match k {
                 ConnOptId::ConnectionID
@@ -56,6 +91,7 @@ impl ConnectOptions {
                 | ConnOptId::NonTransactionalPrepare
                 | ConnOptId::SupportsLargeBulkOperations
                 | ConnOptId::ActiveActiveProtocolVersion
+                | ConnOptId::ActiveActiveConnOriginSite
                 | ConnOptId::ImplicitLobStreaming
                 | ConnOptId::CompleteArrayExecution
                 | ConnOptId::QueryTimeoutOK
@@ -65,8 +101,10 @@ impl ConnectOptions {
                 | ConnOptId::FdaEnabled
                 | ConnOptId::ItabParameter
                 | ConnOptId::ClientDistributionMode
+                | ConnOptId::UpdateTopologyAnwhere
                 | ConnOptId::ClientInfoNullValueOK
                 | ConnOptId::ClientReconnectWaitTimeout
+                | ConnOptId::CompressionLevelAndFlags
                 | ConnOptId::FlagSet1 => {
                     if let Some(old_value) = self.get(&k) {
                         if *old_value != v {
@@ -189,6 +227,34 @@ impl ConnectOptions {
         self.get_integer(&ConnOptId::DataFormatVersion2, "DataFormatVersion2")
     }
 
+    // This field is set by the client to indicate the mode for handling statement
+    // routing and client distribution. The server sets this field to the
+    // appropriate support level depending on the client value and its own
+    // configuration.
+    //
+    // The following values are supported:
+    //
+    //   0 OFF          no routing or distributed transaction handling is done.
+    //   1 CONNECTION   client can connect to any (master/slave) server in the
+    //                  topology, and connections are ena­bled, such that the
+    //                  connection load on the nodes is balanced.
+    //   2 STATEMENT    server returns information about which node is preferred
+    //                  for executing the statement, cli­ents execute on that node,
+    //                  if possible.
+    //   3 STATEMENT_CONNECTION  both STATEMENT and CONNECTION level
+    pub fn get_client_distribution_mode(&self) -> Option<i32> {
+        self.get_integer(&ConnOptId::ClientDistributionMode, "ClientDistributionMode")
+    }
+
+    // Is sent by the client to request network compression, and returned by the server
+    // with a non-zero value if it agrees to use compression.
+    pub fn get_compression_level_and_flags(&self) -> Option<i32> {
+        self.get_integer(
+            &ConnOptId::CompressionLevelAndFlags,
+            "CompressionLevelAndFlags",
+        )
+    }
+
     // // NonTransactionalPrepare
     // pub fn get_nontransactional_prepare(&self) -> Option<&bool> {
     //     self.get_bool(
@@ -205,13 +271,23 @@ impl ConnectOptions {
     //     )
     // }
 
-    // // ActiveActiveProtocolVersion.
-    // pub fn get_activeactive_protocolversion(&self) -> Option<i32> {
-    //     self.get_integer(
-    //         &ConnOptId::ActiveActiveProtocolVersion,
-    //         "ActiveActiveProtocolVersion",
-    //     )
-    // }
+    // Is sent by the client to announce its support for Active/Active (read enabled)
+    // systems, and returned by the server with the version it agrees to use;
+    // a missing value or 0 means that the server does not route to a secondary site.
+    pub fn get_active_active_protocol_version(&self) -> Option<i32> {
+        self.get_integer(
+            &ConnOptId::ActiveActiveProtocolVersion,
+            "ActiveActiveProtocolVersion",
+        )
+    }
+
+    // Is set by the server to the site on which the connection was opened.
+    pub fn get_active_active_conn_origin_site(&self) -> Option<i32> {
+        self.get_integer(
+            &ConnOptId::ActiveActiveConnOriginSite,
+            "ActiveActiveConnOriginSite",
+        )
+    }
 
     // Is set by the server to indicate that it supports implicit LOB streaming
     // even though auto-commit is on instead of raising an error.
@@ -225,10 +301,11 @@ impl ConnectOptions {
     //     self.get_bool(&ConnOptId::CompleteArrayExecution, "CompleteArrayExecution")
     // }
 
-    // // Is set by the server
-    // pub fn get_query_timeout_ok(&self) -> Option<&bool> {
-    //     self.get_bool(&ConnOptId::QueryTimeoutOK, "QueryTimeoutOK")
-    // }
+    // Is set by the server to indicate that it aborts statements
+    // when the query timeout that was sent with them expires.
+    pub fn get_query_timeout_ok(&self) -> Option<bool> {
+        self.get_bool(&ConnOptId::QueryTimeoutOK, "QueryTimeoutOK")
+    }
 
     // // Is set by the server to indicate the client should gather the
     // // state of the current transaction only from the TRANSACTIONFLAGS command, not
@@ -263,25 +340,6 @@ impl ConnectOptions {
     //     self.get_bool(&ConnOptId::ItabParameter, "ItabParameter")
     // }
 
-    // // This field is set by the client to indicate the mode for handling statement
-    // // routing and client distribution. The server sets this field to the
-    // // appropriate support level depending on the client value and its own
-    // // configuration.
-    // //
-    // // The following values are supported:
-    // //
-    // //   0 OFF          no routing or distributed transaction handling is done.
-    // //   1 CONNECTION   client can connect to any (master/slave) server in the
-    // //                  topology, and connections are ena­bled, such that the
-    // //                  connection load on the nodes is balanced.
-    // //   2 STATEMENT    server returns information about which node is preferred
-    // //                  for executing the statement, cli­ents execute on that node,
-    // //                  if possible.
-    // //   3 STATEMENT_CONNECTION  both STATEMENT and CONNECTION level
-    // pub fn get_client_distribution_mode(&self) -> Option<i32> {
-    //     self.get_integer(&ConnOptId::ClientDistributionMode, "ClientDistributionMode")
-    // }
-
     // pub fn get_clientinfo_nullvalue_ok(&self) -> Option<&bool> {
     //     self.get_bool(&ConnOptId::ClientInfoNullValueOK, "ClientInfoNullValueOK")
     // }
@@ -0,0 +1,74 @@
+use crate::protocol::parts::option_part::OptionId;
+use crate::protocol::parts::option_part::OptionPart;
+use crate::protocol::parts::option_value::OptionValue;
+use std::convert::TryFrom;
+
+// An Options part that is used to ask the system database of a multitenant
+// database container where a tenant database can be reached.
+pub(crate) type DbConnectInfo = OptionPart<DbConnectInfoId>;
+
+impl DbConnectInfo {
+    pub fn new(database_name: &str) -> Self {
+        let mut dci = Self::default();
+        dci.insert(
+            DbConnectInfoId::DatabaseName,
+            OptionValue::STRING(database_name.to_string()),
+        );
+        dci
+    }
+
+    pub fn host(&self) -> Option<&str> {
+        match self.get(&DbConnectInfoId::Host) {
+            Some(OptionValue::STRING(s)) => Some(s),
+            _ => None,
+        }
+    }
+
+    pub fn port(&self) -> Option<u16> {
+        match self.get(&DbConnectInfoId::Port) {
+            Some(&OptionValue::INT(i)) => u16::try_from(i).ok(),
+            _ => None,
+        }
+    }
+
+    pub fn is_connected(&self) -> bool {
+        matches!(
+            self.get(&DbConnectInfoId::IsConnected),
+            Some(&OptionValue::BOOLEAN(true))
+        )
+    }
+}
+
+#[derive(Debug, Eq, PartialEq, Hash)]
+pub enum DbConnectInfoId {
+    DatabaseName, // 1 // STRING  // Name of the requested database
+    Host,         // 2 // STRING  // Host on which the database can be reached
+    Port,         // 3 // INT     // SQL port of the database
+    IsConnected,  // 4 // BOOLEAN // Whether the connection is already to the database
+    __Unexpected__(u8),
+}
+
+impl OptionId<DbConnectInfoId> for DbConnectInfoId {
+    fn to_u8(&self) -> u8 {
+        match *self {
+            Self::DatabaseName => 1,
+            Self::Host => 2,
+            Self::Port => 3,
+            Self::IsConnected => 4,
+            Self::__Unexpected__(val) => val,
+        }
+    }
+
+    fn from_u8(val: u8) -> Self {
+        match val {
+            1 => Self::DatabaseName,
+            2 => Self::Host,
+            3 => Self::Port,
+            4 => Self::IsConnected,
+            val => {
+                warn!("Unsupported value for DbConnectInfoId received: {}", val);
+                Self::__Unexpected__(val)
+            }
+        }
+    }
+}
@@ -604,6 +604,10 @@ pub(crate) fn emit_length_and_string(s: &str, w: &mut dyn std::io::Write) -> std
     emit_length_and_bytes(&cesu8::to_cesu8(s), w)
 }
 
+pub(crate) fn emit_null(w: &mut dyn std::io::Write) -> std::io::Result<()> {
+    w.write_u8(LENGTH_INDICATOR_NULL)
+}
+
 #[allow(clippy::cast_possible_truncation)]
 #[allow(clippy::cast_possible_wrap)]
 fn emit_length_and_bytes(v: &[u8], w: &mut dyn std::io::Write) -> std::io::Result<()> {
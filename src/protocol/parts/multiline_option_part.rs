@@ -17,4 +17,8 @@ impl<T: OptionId<T> + Debug + Eq + PartialEq + Hash> MultilineOptionPart<T> {
         }
         Ok(Self(option_parts))
     }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, OptionPart<T>> {
+        self.0.iter()
+    }
 }
@@ -101,7 +101,30 @@ impl RsState {
 
     fn fetch_next(&mut self, a_rsmd: &Arc<ResultSetMetadata>) -> HdbResult<()> {
         trace!("ResultSet::fetch_next()");
-        let (mut conn_core, resultset_id, fetch_size) = {
+        let (mut conn_core, request) = self.fetch_request()?;
+        let mut reply = conn_core.full_send_sync(request, Some(a_rsmd), None, &mut Some(self))?;
+        reply.assert_expected_reply_type(ReplyType::Fetch)?;
+        reply.parts.pop_if_kind(PartKind::ResultSet);
+        self.drop_rs_core_if_complete()
+    }
+
+    #[cfg(feature = "async")]
+    async fn fetch_next_async(
+        am_rs_state: Arc<Mutex<Self>>,
+        a_rsmd: Arc<ResultSetMetadata>,
+    ) -> HdbResult<()> {
+        trace!("ResultSet::fetch_next_async()");
+        let (conn_core, request) = am_rs_state.lock()?.fetch_request()?;
+        let mut reply = conn_core
+            .full_send_async(request, Some(&a_rsmd), None, Some(&am_rs_state))
+            .await?;
+        reply.assert_expected_reply_type(ReplyType::Fetch)?;
+        reply.parts.pop_if_kind(PartKind::ResultSet);
+        am_rs_state.lock()?.drop_rs_core_if_complete()
+    }
+
+    fn fetch_request(&self) -> HdbResult<(AmConnCore, Request<'static>)> {
+        let (conn_core, resultset_id, fetch_size) = {
             // scope the borrow
             if let Some(ref am_rscore) = self.o_am_rscore {
                 let rs_core = am_rscore.lock()?;
@@ -114,15 +137,17 @@ impl RsState {
         };
 
         // build the request, provide resultset-id and fetch-size
-        debug!("ResultSet::fetch_next() with fetch_size = {}", fetch_size);
+        debug!(
+            "ResultSet::fetch_request() with fetch_size = {}",
+            fetch_size
+        );
         let mut request = Request::new(RequestType::FetchNext, 0);
         request.push(Part::ResultSetId(resultset_id));
         request.push(Part::FetchSize(fetch_size));
+        Ok((conn_core, request))
+    }
 
-        let mut reply = conn_core.full_send_sync(request, Some(a_rsmd), None, &mut Some(self))?;
-        reply.assert_expected_reply_type(ReplyType::Fetch)?;
-        reply.parts.pop_if_kind(PartKind::ResultSet);
-
+    fn drop_rs_core_if_complete(&mut self) -> HdbResult<()> {
         let mut drop_rs_core = false;
         if let Some(ref am_rscore) = self.o_am_rscore {
             drop_rs_core = am_rscore.lock()?.attributes.is_last_packet();
@@ -133,6 +158,19 @@ impl RsState {
         Ok(())
     }
 
+    // Returns the next row, if it was already transported from the database.
+    #[cfg(feature = "async")]
+    fn next_fetched_row(&mut self) -> Option<Row> {
+        if let Some(r) = self.row_iter.next() {
+            Some(r)
+        } else {
+            let mut tmp_vec = Vec::<Row>::new();
+            std::mem::swap(&mut tmp_vec, &mut self.next_rows);
+            self.row_iter = tmp_vec.into_iter();
+            self.row_iter.next()
+        }
+    }
+
     fn is_complete(&self) -> HdbResult<bool> {
         if let Some(ref am_rscore) = self.o_am_rscore {
             let rs_core = am_rscore.lock()?;
@@ -211,9 +249,7 @@ impl Drop for ResultSetCore {
         if !self.attributes.resultset_is_closed() {
             let mut request = Request::new(RequestType::CloseResultSet, 0);
             request.push(Part::ResultSetId(rs_id));
-            if let Ok(mut reply) = self.am_conn_core.send_sync(request) {
-                reply.parts.pop_if_kind(PartKind::StatementContext);
-            }
+            self.am_conn_core.send_and_ignore_errors(request);
         }
     }
 }
@@ -351,6 +387,31 @@ impl ResultSet {
         self.state.lock()?.fetch_all(&self.metadata)
     }
 
+    // Returns the next row, if it is available without a roundtrip;
+    // with Ok(None), either the resultset is exhausted or more rows need to be fetched.
+    #[cfg(feature = "async")]
+    pub(crate) fn next_fetched_row(&self) -> HdbResult<Option<Row>> {
+        Ok(self.state.lock()?.next_fetched_row())
+    }
+
+    #[cfg(feature = "async")]
+    pub(crate) fn is_complete(&self) -> HdbResult<bool> {
+        self.state.lock()?.is_complete()
+    }
+
+    #[cfg(feature = "async")]
+    pub(crate) fn number_of_fetched_rows(&self) -> HdbResult<usize> {
+        Ok(self.state.lock()?.len())
+    }
+
+    // The returned future does not borrow from the `ResultSet`.
+    #[cfg(feature = "async")]
+    pub(crate) fn fetch_next_async(
+        &self,
+    ) -> impl std::future::Future<Output = HdbResult<()>> + Send + 'static {
+        RsState::fetch_next_async(Arc::clone(&self.state), Arc::clone(&self.metadata))
+    }
+
     pub(crate) fn has_multiple_rows_impl(&self) -> bool {
         self.state
             .lock()
@@ -0,0 +1,41 @@
+use crate::protocol::parts::hdb_value::{emit_length_and_string, emit_null, string_length};
+
+// A part that sets session variables, or unsets them by sending them with a NULL value.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct SessionVariables(Vec<(String, Option<String>)>);
+
+impl SessionVariables {
+    // A later change of the same variable replaces an earlier one.
+    pub fn set(&mut self, name: &str, o_value: Option<&str>) {
+        self.0.retain(|(n, _)| n != name);
+        self.0
+            .push((name.to_string(), o_value.map(ToString::to_string)));
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn emit(&self, w: &mut dyn std::io::Write) -> std::io::Result<()> {
+        for (name, o_value) in &self.0 {
+            emit_length_and_string(name, w)?;
+            match o_value {
+                Some(value) => emit_length_and_string(value, w)?,
+                None => emit_null(w)?,
+            }
+        }
+        Ok(())
+    }
+
+    pub fn size(&self) -> usize {
+        let mut len = 0;
+        for (name, o_value) in &self.0 {
+            len += string_length(name) + o_value.as_ref().map_or(1, |value| string_length(value));
+        }
+        len
+    }
+
+    pub fn count(&self) -> usize {
+        self.0.len() * 2
+    }
+}
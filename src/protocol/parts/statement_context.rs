@@ -5,7 +5,7 @@ use std::time::Duration;
 
 // An options part that is populated from previously received statement context
 // information. The binary option content is opaque to the client.
-// Assumption: it's used to support automatic reconnects (but we don't make use of that: TODO)
+// The schema name is used to restore the current schema after a reconnect.
 pub(crate) type StatementContext = OptionPart<StatementContextId>;
 
 impl StatementContext {
@@ -23,6 +23,32 @@ impl StatementContext {
         );
     }
 
+    pub fn schema_name(&self) -> Option<&str> {
+        match self.get(&StatementContextId::SchemaName) {
+            Some(OptionValue::STRING(s)) => Some(s),
+            _ => None,
+        }
+    }
+
+    // The server expects the query timeout in seconds; fractions are rounded up.
+    pub fn set_query_timeout(&mut self, query_timeout: Duration) {
+        let seconds = query_timeout.as_secs() + u64::from(query_timeout.subsec_nanos() > 0);
+        self.insert(
+            StatementContextId::QueryTimeout,
+            OptionValue::BIGINT(seconds.try_into().unwrap_or(i64::MAX)),
+        );
+    }
+
+    // The time (in seconds) within which the server accepts a reconnect, e.g. during a takeover.
+    pub fn client_reconnection_wait_timeout(&self) -> Option<Duration> {
+        match self.get(&StatementContextId::ClientReconnectionWaitTimeout) {
+            Some(&OptionValue::INT(value)) => {
+                Some(Duration::from_secs(value.try_into().unwrap_or(0)))
+            }
+            _ => None,
+        }
+    }
+
     pub fn server_processing_time(&self) -> Option<Duration> {
         match self.get(&StatementContextId::ServerProcessingTime) {
             Some(&OptionValue::BIGINT(value)) => {
@@ -1,8 +1,196 @@
 use crate::protocol::parts::multiline_option_part::MultilineOptionPart;
-use crate::protocol::parts::option_part::OptionId;
+use crate::protocol::parts::option_part::{OptionId, OptionPart};
+use crate::protocol::parts::option_value::OptionValue;
+use std::convert::TryFrom;
 
+// Describes the hosts of the database landscape, one line per host.
 pub type Topology = MultilineOptionPart<TopologyAttrId>;
 
+impl Topology {
+    pub fn to_host_descriptors(&self) -> Vec<HostDescriptor> {
+        self.iter()
+            .filter_map(HostDescriptor::from_topology_line)
+            .collect()
+    }
+}
+
+/// Describes a host of the database landscape, as reported by the server.
+///
+/// See [`Connection::topology`](struct.Connection.html#method.topology).
+#[derive(Clone, Debug, PartialEq)]
+pub struct HostDescriptor {
+    host: String,
+    port: u16,
+    tenant: Option<String>,
+    load_factor: Option<f64>,
+    volume_id: Option<i32>,
+    is_master: bool,
+    is_current_session: bool,
+    service_type: ServiceType,
+    is_standby: bool,
+    site_type: SiteType,
+}
+impl HostDescriptor {
+    fn from_topology_line(line: &OptionPart<TopologyAttrId>) -> Option<Self> {
+        let get_string = |id| match line.get(&id) {
+            Some(OptionValue::STRING(s)) => Some(s.clone()),
+            _ => None,
+        };
+        let get_int = |id| match line.get(&id) {
+            Some(OptionValue::INT(i)) => Some(*i),
+            _ => None,
+        };
+        let get_bool = |id| matches!(line.get(&id), Some(OptionValue::BOOLEAN(true)));
+
+        let host = get_string(TopologyAttrId::HostName);
+        let port = get_int(TopologyAttrId::HostPortNumber).and_then(|i| u16::try_from(i).ok());
+        if let (Some(host), Some(port)) = (host, port) {
+            Some(Self {
+                host,
+                port,
+                tenant: get_string(TopologyAttrId::TenantName),
+                load_factor: if let Some(OptionValue::DOUBLE(f)) =
+                    line.get(&TopologyAttrId::LoadFactor)
+                {
+                    Some(*f)
+                } else {
+                    None
+                },
+                volume_id: get_int(TopologyAttrId::VolumeID),
+                is_master: get_bool(TopologyAttrId::IsMaster),
+                is_current_session: get_bool(TopologyAttrId::IsCurrentSession),
+                service_type: ServiceType::from_i32(
+                    get_int(TopologyAttrId::ServiceType).unwrap_or(0),
+                ),
+                is_standby: get_bool(TopologyAttrId::IsStandby),
+                site_type: SiteType::from_i32(get_int(TopologyAttrId::SiteType).unwrap_or(0)),
+            })
+        } else {
+            warn!(
+                "Ignoring topology information without host and port: {:?}",
+                line
+            );
+            None
+        }
+    }
+
+    /// The host name.
+    pub fn host(&self) -> &str {
+        &self.host
+    }
+
+    /// The SQL port on the host.
+    pub fn port(&self) -> u16 {
+        self.port
+    }
+
+    /// The socket address.
+    pub fn addr(&self) -> String {
+        format!("{}:{}", self.host, self.port)
+    }
+
+    /// The name of the tenant database.
+    pub fn tenant(&self) -> Option<&str> {
+        self.tenant.as_deref()
+    }
+
+    /// The load factor of the host.
+    pub fn load_factor(&self) -> Option<f64> {
+        self.load_factor
+    }
+
+    /// The id of the volume that is served by the host.
+    pub fn volume_id(&self) -> Option<i32> {
+        self.volume_id
+    }
+
+    /// Whether the host is the master of the system.
+    pub fn is_master(&self) -> bool {
+        self.is_master
+    }
+
+    /// Whether the session of the connection runs on this host.
+    pub fn is_current_session(&self) -> bool {
+        self.is_current_session
+    }
+
+    /// The type of the service.
+    pub fn service_type(&self) -> ServiceType {
+        self.service_type
+    }
+
+    /// Whether the host is a standby host.
+    pub fn is_standby(&self) -> bool {
+        self.is_standby
+    }
+
+    /// The site of the host in a system replication setup.
+    pub fn site_type(&self) -> SiteType {
+        self.site_type
+    }
+}
+
+/// The type of service that a host provides.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ServiceType {
+    /// Unknown or other service.
+    Other,
+    /// Name server.
+    NameServer,
+    /// Preprocessor.
+    Preprocessor,
+    /// Index server.
+    IndexServer,
+    /// Statistics server.
+    StatisticsServer,
+    /// XS engine.
+    XSEngine,
+    /// Compile server.
+    CompileServer,
+    /// Data provisioning server.
+    DPServer,
+    /// Data integration server.
+    DIServer,
+}
+impl ServiceType {
+    fn from_i32(val: i32) -> Self {
+        match val {
+            1 => Self::NameServer,
+            2 => Self::Preprocessor,
+            3 => Self::IndexServer,
+            4 => Self::StatisticsServer,
+            5 => Self::XSEngine,
+            7 => Self::CompileServer,
+            8 => Self::DPServer,
+            9 => Self::DIServer,
+            _ => Self::Other,
+        }
+    }
+}
+
+/// The site of a host in a system replication setup.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum SiteType {
+    /// No system replication.
+    None,
+    /// The primary site.
+    Primary,
+    /// The secondary site.
+    Secondary,
+    /// The tertiary site.
+    Tertiary,
+}
+impl SiteType {
+    fn from_i32(val: i32) -> Self {
+        match val {
+            1 => Self::Primary,
+            2 => Self::Secondary,
+            3 => Self::Tertiary,
+            _ => Self::None,
+        }
+    }
+}
+
 #[derive(Clone, Debug, Eq, PartialEq, Hash)]
 pub enum TopologyAttrId {
     HostName,         //  1 // host name
@@ -58,31 +246,43 @@ impl OptionId<TopologyAttrId> for TopologyAttrId {
     }
 }
 
-/*
-        // Service type: all types are listed for completeness, even
-        // if only some are used right now (index server, statistics server)
-        enum ServiceType
-        {
-            ServiceType_Other            = 0, // sink type for unknown etc.
-            ServiceType_NameServer       = 1,
-            ServiceType_Preprocessor     = 2,
-            ServiceType_IndexServer      = 3,
-            ServiceType_StatisticsServer = 4,
-            ServiceType_XSEngine         = 5,
-            ServiceType___reserved__6    = 6,
-            ServiceType_CompileServer    = 7,
-            ServiceType_DPServer         = 8,
-            ServiceType_DIServer         = 9,
-            ServiceType_Last
-        };
+#[cfg(test)]
+mod tests {
+    use super::{SiteType, Topology, TopologyAttrId};
+    use crate::protocol::parts::option_part::OptionPart;
+    use crate::protocol::parts::option_value::OptionValue;
+    use byteorder::{LittleEndian, WriteBytesExt};
 
-        // Site type enum used with TopologyInformation_SiteType and ConnectOption_ActiveActiveConnectionOriginSite
-        enum SiteType
+    #[test]
+    fn test_host_descriptors() {
+        let mut buf = Vec::<u8>::new();
+        for (host, port, volume_id, is_current) in
+            &[("host1", 30040, 2, false), ("host2", 30041, 3, true)]
         {
-            SiteType_None                = 0,   // no HSR
-            SiteType_Primary             = 1,
-            SiteType_Secondary           = 2,
-            SiteType_Tertiary            = 3,
-            SiteType_Last
-        };
-*/
+            let mut line = OptionPart::<TopologyAttrId>::default();
+            line.insert(
+                TopologyAttrId::HostName,
+                OptionValue::STRING((*host).to_string()),
+            );
+            line.insert(TopologyAttrId::HostPortNumber, OptionValue::INT(*port));
+            line.insert(TopologyAttrId::VolumeID, OptionValue::INT(*volume_id));
+            line.insert(
+                TopologyAttrId::IsCurrentSession,
+                OptionValue::BOOLEAN(*is_current),
+            );
+            buf.write_u16::<LittleEndian>(line.len() as u16).unwrap();
+            line.emit(&mut buf).unwrap();
+        }
+
+        let hosts = Topology::parse(2, &mut std::io::Cursor::new(buf))
+            .unwrap()
+            .to_host_descriptors();
+        assert_eq!(hosts.len(), 2);
+        assert_eq!(hosts[0].addr(), "host1:30040");
+        assert_eq!(hosts[0].volume_id(), Some(2));
+        assert!(!hosts[0].is_current_session());
+        assert_eq!(hosts[1].addr(), "host2:30041");
+        assert!(hosts[1].is_current_session());
+        assert_eq!(hosts[1].site_type(), SiteType::None);
+    }
+}
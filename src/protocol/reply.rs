@@ -3,7 +3,7 @@ use crate::hdb_response::InternalReturnValue;
 use crate::protocol::parts::{
     ExecutionResult, ParameterDescriptors, Parts, ResultSetMetadata, RsState, ServerError, Severity,
 };
-use crate::protocol::{util, Part, PartKind, ReplyType, ServerUsage};
+use crate::protocol::{compression, util, Part, PartKind, ReplyType, ServerUsage};
 use crate::{HdbError, HdbResult};
 use byteorder::{LittleEndian, ReadBytesExt};
 use std::sync::Arc;
@@ -44,7 +44,40 @@ impl Reply {
         rdr: &mut dyn std::io::Read,
     ) -> std::io::Result<Self> {
         trace!("Reply::parse()");
-        let (no_of_parts, mut reply) = parse_message_and_sequence_header(rdr)?;
+        let message_header = MessageHeader::parse(rdr)?;
+        if let Some(uncompressed_length) = message_header.o_uncompressed_length {
+            let compressed = util::parse_bytes(message_header.varpart_length as usize, rdr)?;
+            let varpart = compression::decompress(&compressed, uncompressed_length as usize)?;
+            Self::parse_varpart(
+                message_header.session_id,
+                o_a_rsmd,
+                o_a_descriptors,
+                o_rs,
+                o_am_conn_core,
+                &mut std::io::Cursor::new(varpart),
+            )
+        } else {
+            Self::parse_varpart(
+                message_header.session_id,
+                o_a_rsmd,
+                o_a_descriptors,
+                o_rs,
+                o_am_conn_core,
+                rdr,
+            )
+        }
+    }
+
+    // Parse the segment header and the parts.
+    fn parse_varpart(
+        session_id: i64,
+        o_a_rsmd: Option<&Arc<ResultSetMetadata>>,
+        o_a_descriptors: Option<&Arc<ParameterDescriptors>>,
+        o_rs: &mut Option<&mut RsState>,
+        o_am_conn_core: Option<&AmConnCore>,
+        rdr: &mut dyn std::io::Read,
+    ) -> std::io::Result<Self> {
+        let (no_of_parts, mut reply) = parse_segment_header(session_id, rdr)?;
 
         for i in 0..no_of_parts {
             let part = Part::parse(
@@ -169,23 +202,57 @@ impl Reply {
     }
 }
 
-fn parse_message_and_sequence_header(rdr: &mut dyn std::io::Read) -> std::io::Result<(i16, Reply)> {
+struct MessageHeader {
+    session_id: i64,
+    varpart_length: u32,
+    o_uncompressed_length: Option<u32>,
+}
+impl MessageHeader {
     // MESSAGE HEADER: 32 bytes
-    let session_id: i64 = rdr.read_i64::<LittleEndian>()?; // I8
-    let packet_seq_number: i32 = rdr.read_i32::<LittleEndian>()?; // I4
-    let varpart_size: u32 = rdr.read_u32::<LittleEndian>()?; // UI4  not needed?
-    let remaining_bufsize: u32 = rdr.read_u32::<LittleEndian>()?; // UI4  not needed?
-    let no_of_segs = rdr.read_i16::<LittleEndian>()?; // I2
-    if no_of_segs == 0 {
-        return Err(util::io_error("empty response (is ok for drop connection)"));
-    }
+    fn parse(rdr: &mut dyn std::io::Read) -> std::io::Result<Self> {
+        let session_id: i64 = rdr.read_i64::<LittleEndian>()?; // I8
+        let packet_seq_number: i32 = rdr.read_i32::<LittleEndian>()?; // I4
+        let varpart_length: u32 = rdr.read_u32::<LittleEndian>()?; // UI4
+        let remaining_bufsize: u32 = rdr.read_u32::<LittleEndian>()?; // UI4  not needed?
+        let no_of_segs = rdr.read_i16::<LittleEndian>()?; // I2
+        if no_of_segs == 0 {
+            return Err(util::io_error("empty response (is ok for drop connection)"));
+        }
 
-    if no_of_segs > 1 {
-        return Err(util::io_error(format!("no_of_segs = {} > 1", no_of_segs)));
-    }
+        if no_of_segs > 1 {
+            return Err(util::io_error(format!("no_of_segs = {} > 1", no_of_segs)));
+        }
+
+        let packet_options = rdr.read_u8()?; // I1
+        util::skip_bytes(1, rdr)?; // B1 filler
+        let compression_varpart_length = rdr.read_u32::<LittleEndian>()?; // UI4
+        util::skip_bytes(4, rdr)?; // B[4]
 
-    util::skip_bytes(10, rdr)?; // (I1 + B[9])
+        trace!(
+            "message header: {{ packet_seq_number = {}, varpart_length = {}, \
+             remaining_bufsize = {}, packet_options = {} }}",
+            packet_seq_number,
+            varpart_length,
+            remaining_bufsize,
+            packet_options
+        );
 
+        Ok(Self {
+            session_id,
+            varpart_length,
+            o_uncompressed_length: if packet_options & compression::PACKET_OPTION_COMPRESSED == 0 {
+                None
+            } else {
+                Some(compression_varpart_length)
+            },
+        })
+    }
+}
+
+fn parse_segment_header(
+    session_id: i64,
+    rdr: &mut dyn std::io::Read,
+) -> std::io::Result<(i16, Reply)> {
     // SEGMENT HEADER: 24 bytes
     rdr.read_i32::<LittleEndian>()?; // I4 seg_size
     rdr.read_i32::<LittleEndian>()?; // I4 seg_offset
@@ -193,14 +260,7 @@ fn parse_message_and_sequence_header(rdr: &mut dyn std::io::Read) -> std::io::Re
     rdr.read_i16::<LittleEndian>()?; // I2 seg_number
     let seg_kind = Kind::from_i8(rdr.read_i8()?)?; // I1
 
-    trace!(
-        "message and segment header: {{ packet_seq_number = {}, varpart_size = {}, \
-         remaining_bufsize = {}, no_of_parts = {} }}",
-        packet_seq_number,
-        varpart_size,
-        remaining_bufsize,
-        no_of_parts
-    );
+    trace!("segment header: {{ no_of_parts = {} }}", no_of_parts);
 
     match seg_kind {
         Kind::Request => Err(util::io_error("Cannot _parse_ a request".to_string())),
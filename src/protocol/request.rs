@@ -2,11 +2,12 @@
 //! we model message and segment together.
 //! But we differentiate explicitly between request messages and reply messages.
 use crate::protocol::parts::{ParameterDescriptors, Parts, StatementContext};
-use crate::protocol::{Part, RequestType};
+use crate::protocol::{compression, Part, RequestType};
 use byteorder::{LittleEndian, WriteBytesExt};
 use std::sync::Arc;
+use std::time::Duration;
 
-const MESSAGE_HEADER_SIZE: u32 = 32;
+pub const MESSAGE_HEADER_SIZE: u32 = 32;
 const SEGMENT_HEADER_SIZE: usize = 24; // same for in and out
 pub const HOLD_CURSORS_OVER_COMMIT: u8 = 8;
 
@@ -16,6 +17,7 @@ pub(crate) struct Request<'a> {
     pub request_type: RequestType,
     command_options: u8,
     parts: Parts<'a>,
+    o_query_timeout: Option<Duration>,
 }
 // Methods for defining a request
 impl<'a> Request<'a> {
@@ -24,6 +26,7 @@ impl<'a> Request<'a> {
             request_type,
             command_options,
             parts: Parts::default(),
+            o_query_timeout: None,
         }
     }
 
@@ -35,44 +38,91 @@ impl<'a> Request<'a> {
         self.parts.push(part);
     }
 
-    pub fn add_statement_context(&mut self, ssi_value: i64) {
+    // Overrides the query timeout of the connection for this request;
+    // Duration::ZERO switches the query timeout off.
+    pub fn set_query_timeout(&mut self, query_timeout: Duration) {
+        self.o_query_timeout = Some(query_timeout);
+    }
+
+    pub fn query_timeout(&self) -> Option<Duration> {
+        self.o_query_timeout
+    }
+
+    pub fn add_statement_context(
+        &mut self,
+        o_ssi_value: Option<i64>,
+        o_query_timeout: Option<Duration>,
+    ) {
         let mut stmt_ctx = StatementContext::default();
-        stmt_ctx.set_statement_sequence_info(ssi_value);
+        if let Some(ssi_value) = o_ssi_value {
+            stmt_ctx.set_statement_sequence_info(ssi_value);
+        }
+        if let Some(query_timeout) = o_query_timeout {
+            stmt_ctx.set_query_timeout(query_timeout);
+        }
         trace!(
-            "Sending StatementContext with sequence_info = {:?}",
-            ssi_value
+            "Sending StatementContext with sequence_info = {:?}, query_timeout = {:?}",
+            o_ssi_value,
+            o_query_timeout
         );
         self.push(Part::StatementContext(stmt_ctx));
     }
 
     #[allow(clippy::cast_possible_truncation)]
-    #[allow(clippy::cast_possible_wrap)]
     pub fn emit(
         &self,
         session_id: i64,
         seq_number: i32,
         auto_commit: bool,
+        compress: bool,
         o_a_descriptors: Option<&Arc<ParameterDescriptors>>,
         w: &mut dyn std::io::Write,
     ) -> std::io::Result<()> {
         let varpart_size = self.varpart_size(o_a_descriptors)?;
         let total_size = MESSAGE_HEADER_SIZE + varpart_size;
         trace!("Writing request with total size {}", total_size);
-        let mut remaining_bufsize = total_size - MESSAGE_HEADER_SIZE;
 
         debug!(
             "Request::emit() of type {:?} for session_id = {}, seq_number = {}",
             self.request_type, session_id, seq_number
         );
-        // MESSAGE HEADER
-        w.write_i64::<LittleEndian>(session_id)?; // I8
-        w.write_i32::<LittleEndian>(seq_number)?; // I4
-        w.write_u32::<LittleEndian>(varpart_size)?; // UI4
-        w.write_u32::<LittleEndian>(remaining_bufsize)?; // UI4
-        w.write_i16::<LittleEndian>(1)?; // I2    Number of segments
-        for _ in 0..10 {
-            w.write_u8(0)?;
-        } // I1+ B[9]  unused
+
+        if compress {
+            let mut varpart = Vec::<u8>::with_capacity(varpart_size as usize);
+            self.emit_varpart(varpart_size, auto_commit, o_a_descriptors, &mut varpart)?;
+            if let Some(compressed) = compression::compress(&varpart) {
+                emit_message_header(
+                    session_id,
+                    seq_number,
+                    compressed.len() as u32,
+                    Some(varpart_size),
+                    w,
+                )?;
+                w.write_all(&compressed)?;
+            } else {
+                emit_message_header(session_id, seq_number, varpart_size, None, w)?;
+                w.write_all(&varpart)?;
+            }
+        } else {
+            emit_message_header(session_id, seq_number, varpart_size, None, w)?;
+            self.emit_varpart(varpart_size, auto_commit, o_a_descriptors, w)?;
+        }
+        w.flush()?;
+        trace!("Parts are written");
+        Ok(())
+    }
+
+    // Writes segment header and parts.
+    #[allow(clippy::cast_possible_truncation)]
+    #[allow(clippy::cast_possible_wrap)]
+    fn emit_varpart(
+        &self,
+        varpart_size: u32,
+        auto_commit: bool,
+        o_a_descriptors: Option<&Arc<ParameterDescriptors>>,
+        w: &mut dyn std::io::Write,
+    ) -> std::io::Result<()> {
+        let mut remaining_bufsize = varpart_size;
 
         // SEGMENT HEADER
         let parts_len = self.parts.len() as i16;
@@ -95,8 +145,6 @@ impl<'a> Request<'a> {
         for part in self.parts.ref_inner() {
             remaining_bufsize = part.emit(remaining_bufsize, o_a_descriptors, w)?;
         }
-        w.flush()?;
-        trace!("Parts are written");
         Ok(())
     }
 
@@ -124,3 +172,29 @@ impl<'a> Request<'a> {
         Ok(len)
     }
 }
+
+// If the varpart is sent compressed, o_uncompressed_size must provide its original size.
+fn emit_message_header(
+    session_id: i64,
+    seq_number: i32,
+    varpart_length: u32,
+    o_uncompressed_size: Option<u32>,
+    w: &mut dyn std::io::Write,
+) -> std::io::Result<()> {
+    w.write_i64::<LittleEndian>(session_id)?; // I8
+    w.write_i32::<LittleEndian>(seq_number)?; // I4
+    w.write_u32::<LittleEndian>(varpart_length)?; // UI4
+    w.write_u32::<LittleEndian>(varpart_length)?; // UI4
+    w.write_i16::<LittleEndian>(1)?; // I2    Number of segments
+    if let Some(uncompressed_size) = o_uncompressed_size {
+        w.write_u8(compression::PACKET_OPTION_COMPRESSED)?; // I1 packet options
+        w.write_u8(0)?; // B1 filler
+        w.write_u32::<LittleEndian>(uncompressed_size)?; // UI4 uncompressed varpart length
+        w.write_u32::<LittleEndian>(0)?; // B[4] unused
+    } else {
+        for _ in 0..10 {
+            w.write_u8(0)?;
+        } // I1+ B[9]  unused
+    }
+    Ok(())
+}
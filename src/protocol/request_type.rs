@@ -8,12 +8,14 @@ pub enum RequestType {
     Execute = 13,         // Execute a previously prepared SQL statement
     ReadLob = 16,         // Reads large object data
     WriteLob = 17,        // Writes large object data
+    Ping = 25,            // Checks that the session is alive, without executing SQL
     Authenticate = 65,    // Sends authentication data
     Connect = 66,         // Connects to the database
     CloseResultSet = 69,  // Closes resultset
     DropStatementId = 70, // Drops prepared statement identifier
     FetchNext = 71,       // Fetches next data from resultset
     Disconnect = 77,      // Disconnects session
+    DbConnectInfo = 82,   // Request/receive database connect information
     XAStart = 83,
     XAEnd = 84,
     XAPrepare = 85,
@@ -30,5 +32,4 @@ pub enum RequestType {
     // FetchRelative = 73,   // Like above, but moves the cursor relative to the current position
     // FetchFirst = 74,      // Moves the cursor to the first row and fetches the data
     // FetchLast = 75,       // Moves the cursor to the last row and fetches the data
-    // DbConnectInfo = 82,   // Request/receive database connect information
 }
@@ -0,0 +1,103 @@
+// Prepared statements of a connection, by the current schema and their SQL text
+// (unqualified table names are resolved in the current schema when a statement is prepared).
+//
+// The cached statements are handed out as further handles to the same server-side
+// statement; the server-side statement is dropped when it is evicted from the cache
+// and all handles are gone.
+use crate::PreparedStatement;
+use std::collections::HashMap;
+
+#[derive(Debug, Default)]
+pub(crate) struct StatementCache {
+    capacity: usize,
+    tick: u64,
+    session_generation: u64,
+    entries: HashMap<CacheKey, CacheEntry>,
+}
+
+type CacheKey = (Option<String>, String);
+
+#[derive(Debug)]
+struct CacheEntry {
+    prepared_statement: PreparedStatement,
+    last_used: u64,
+}
+
+impl StatementCache {
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    // Capacity 0 switches the cache off.
+    pub fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity;
+        while self.entries.len() > capacity {
+            self.evict_least_recently_used();
+        }
+    }
+
+    // Returns a handle to the cached statement.
+    //
+    // A changed session generation means that a reconnect happened, and that the cached
+    // statements do not exist anymore on the server.
+    pub fn get(
+        &mut self,
+        schema: Option<&str>,
+        stmt: &str,
+        session_generation: u64,
+    ) -> Option<PreparedStatement> {
+        if session_generation != self.session_generation {
+            if !self.entries.is_empty() {
+                debug!("StatementCache: invalidated after reconnect");
+                self.entries.clear();
+            }
+            self.session_generation = session_generation;
+        }
+        self.tick += 1;
+        let tick = self.tick;
+        self.entries.get_mut(&key(schema, stmt)).map(|entry| {
+            trace!("StatementCache: hit for {}", stmt);
+            entry.last_used = tick;
+            entry.prepared_statement.share()
+        })
+    }
+
+    pub fn insert(
+        &mut self,
+        schema: Option<&str>,
+        stmt: &str,
+        prepared_statement: &PreparedStatement,
+    ) {
+        if self.capacity == 0 {
+            return;
+        }
+        let key = key(schema, stmt);
+        if !self.entries.contains_key(&key) && self.entries.len() >= self.capacity {
+            self.evict_least_recently_used();
+        }
+        self.entries.insert(
+            key,
+            CacheEntry {
+                prepared_statement: prepared_statement.share(),
+                last_used: self.tick,
+            },
+        );
+    }
+
+    // Dropping the evicted statement drops it on the server, unless it is still in use.
+    fn evict_least_recently_used(&mut self) {
+        if let Some(key) = self
+            .entries
+            .iter()
+            .min_by_key(|(_, entry)| entry.last_used)
+            .map(|(key, _)| key.clone())
+        {
+            trace!("StatementCache: evicting {}", key.1);
+            self.entries.remove(&key);
+        }
+    }
+}
+
+fn key(schema: Option<&str>, stmt: &str) -> CacheKey {
+    (schema.map(ToString::to_string), stmt.to_string())
+}
@@ -34,9 +34,18 @@ use crate::{ConnectParams, Connection, HdbError, HdbResult, IntoConnectParams};
 ///
 /// ```
 ///
+/// Idle connections in the pool can be kept alive with
+/// [`ConnectParamsBuilder::heartbeat_interval`](struct.ConnectParamsBuilder.html#method.heartbeat_interval)
+/// and [`ConnectParamsBuilder::tcp_keepalive`](struct.ConnectParamsBuilder.html#method.tcp_keepalive).
+///
+/// Connections on which a fatal error occured are discarded when they are returned to the pool.
+/// How connections are validated before they are handed out can be configured with
+/// [`set_validation`](struct.ConnectionManager.html#method.set_validation).
+///
 #[derive(Debug)]
 pub struct ConnectionManager {
     connect_params: ConnectParams,
+    validation: ConnectionValidation,
 }
 
 impl ConnectionManager {
@@ -48,8 +57,39 @@ impl ConnectionManager {
     pub fn new<P: IntoConnectParams>(p: P) -> HdbResult<Self> {
         Ok(Self {
             connect_params: p.into_connect_params()?,
+            validation: ConnectionValidation::default(),
         })
     }
+
+    /// Defines how connections are validated before they are handed out by the pool.
+    ///
+    /// Note that r2d2 only validates connections if `test_on_check_out` is active,
+    /// which is the default.
+    pub fn set_validation(&mut self, validation: ConnectionValidation) -> &mut Self {
+        self.validation = validation;
+        self
+    }
+
+    /// Getter
+    pub fn get_validation(&self) -> &ConnectionValidation {
+        &self.validation
+    }
+}
+
+/// Describes how the [`ConnectionManager`](struct.ConnectionManager.html)
+/// validates a connection.
+#[derive(Clone, Debug)]
+pub enum ConnectionValidation {
+    /// Executes the given query; this is the default,
+    /// with `SELECT 'IsConnectionStillAlive' from dummy`.
+    Query(String),
+    /// Uses a protocol-level ping, which does not execute a SQL statement.
+    Ping,
+}
+impl Default for ConnectionValidation {
+    fn default() -> Self {
+        Self::Query("SELECT 'IsConnectionStillAlive' from dummy".to_string())
+    }
 }
 
 impl r2d2::ManageConnection for ConnectionManager {
@@ -66,8 +106,10 @@ impl r2d2::ManageConnection for ConnectionManager {
     // A standard implementation would check if a simple query like SELECT 1 succeeds.
     fn is_valid(&self, conn: &mut Self::Connection) -> Result<(), Self::Error> {
         trace!("ConnectionManager::is_valid()");
-        conn.query("SELECT 'IsConnectionStillAlive' from dummy")
-            .map(|_| ())
+        match self.validation {
+            ConnectionValidation::Query(ref query) => conn.query(query).map(|_| ()),
+            ConnectionValidation::Ping => conn.ping(),
+        }
     }
 
     // *Quickly* determines if the connection is no longer usable.
@@ -75,8 +117,8 @@ impl r2d2::ManageConnection for ConnectionManager {
     // so it should not block. If it returns true, the connection will be discarded.
     // For example, an implementation might check if the underlying TCP socket has disconnected.
     // Implementations that do not support this kind of fast health check may simply return false.
-    fn has_broken(&self, _conn: &mut Self::Connection) -> bool {
+    fn has_broken(&self, conn: &mut Self::Connection) -> bool {
         trace!("ConnectionManager::has_broken()");
-        false // TODO
+        conn.is_broken().unwrap_or(true)
     }
 }
@@ -4,12 +4,13 @@ use crate::protocol::parts::{
     HdbValue, LobFlags, ParameterDescriptors, ParameterRows, ResultSetMetadata, TypeId,
 };
 use crate::protocol::{
-    Part, PartKind, Request, RequestType, ServerUsage, HOLD_CURSORS_OVER_COMMIT,
+    Part, PartKind, Reply, ReplyType, Request, RequestType, ServerUsage, HOLD_CURSORS_OVER_COMMIT,
 };
 use crate::types_impl::lob::LobWriter;
 use crate::{HdbError, HdbResponse, HdbResult};
 use std::io::Write;
 use std::sync::{Arc, Mutex};
+use std::time::Duration;
 
 /// Allows injection-safe SQL execution and repeated calls of the same statement
 /// with different parameters with as few roundtrips as possible.
@@ -76,7 +77,18 @@ pub struct PreparedStatement {
     a_descriptors: Arc<ParameterDescriptors>,
     o_a_rsmd: Option<Arc<ResultSetMetadata>>,
     batch: ParameterRows<'static>,
-    _o_table_location: Option<Vec<i32>>,
+    o_table_location: Option<Vec<i32>>,
+    o_query_timeout: Option<Duration>,
+    o_routing: Option<Routing>,
+}
+
+// A statement that was prepared on another connection than the one on which it was
+// requested, with statement routing or read-only routing.
+#[derive(Clone, Debug)]
+struct Routing {
+    primary: AmConnCore,
+    stmt: String,
+    read_only: bool,
 }
 
 pub(crate) type AmPsCore = Arc<Mutex<PreparedStatementCore>>;
@@ -86,6 +98,7 @@ pub(crate) type AmPsCore = Arc<Mutex<PreparedStatementCore>>;
 pub(crate) struct PreparedStatementCore {
     am_conn_core: AmConnCore,
     statement_id: u64,
+    session_generation: u64,
 }
 
 impl<'a> PreparedStatement {
@@ -138,12 +151,19 @@ impl<'a> PreparedStatement {
     /// Several variants of `HdbError` can occur.
     pub fn execute<T: serde::ser::Serialize>(&mut self, input: &T) -> HdbResult<HdbResponse> {
         trace!("PreparedStatement::execute()");
-        if self.a_descriptors.has_in() {
-            let mut par_rows = ParameterRows::new();
-            par_rows.push(input, &self.a_descriptors)?;
-            return self.execute_parameter_rows(Some(par_rows));
+        let o_rows = self.parameter_rows_from(input)?;
+        match self.execute_parameter_rows(o_rows) {
+            Err(e) if self.is_on_broken_companion()? => {
+                warn!(
+                    "Secondary site failed, statement is executed on the primary: {}",
+                    e
+                );
+                self.prepare_on_primary()?;
+                let o_rows = self.parameter_rows_from(input)?;
+                self.execute_parameter_rows(o_rows)
+            }
+            result => result,
         }
-        self.execute_parameter_rows(None)
     }
 
     /// Consumes the given `HdbValue`s as a row of parameters for immediate execution.
@@ -195,100 +215,38 @@ impl<'a> PreparedStatement {
     ///
     /// Several variants of `HdbError` can occur.
     pub fn execute_row(&'a mut self, hdb_values: Vec<HdbValue<'a>>) -> HdbResult<HdbResponse> {
+        self.check_routing()?;
         if self.a_descriptors.has_in() {
-            let mut ps_core_guard = self.am_ps_core.lock()?;
-
-            let mut request = Request::new(RequestType::Execute, HOLD_CURSORS_OVER_COMMIT);
-
-            request.push(Part::StatementId(ps_core_guard.statement_id));
-
-            // If readers were provided, pick them out and replace them with None
-            let mut readers: Vec<(HdbValue, TypeId)> = vec![];
-            let hdb_values = hdb_values
-                .into_iter()
-                .zip(self.a_descriptors.iter_in())
-                .map(|(v, d)| {
-                    if let HdbValue::LOBSTREAM(Some(_)) = v {
-                        readers.push((v, d.type_id()));
-                        HdbValue::LOBSTREAM(None)
-                    } else {
-                        v
-                    }
-                })
-                .collect();
-
-            let mut par_rows = ParameterRows::new();
-            par_rows.push_hdb_values(hdb_values, &self.a_descriptors)?;
-            request.push(Part::Parameters(par_rows));
-
-            if ps_core_guard
-                .am_conn_core
-                .lock()?
-                .connect_options()
-                .get_implicit_lob_streaming()
-                .unwrap_or(false)
-            {
-                request.push(Part::LobFlags(LobFlags::for_implicit_streaming()));
-            }
-
-            let mut main_reply = ps_core_guard.am_conn_core.full_send_sync(
+            let (mut am_conn_core, request, readers) = self.execute_row_request(hdb_values)?;
+            let main_reply = am_conn_core.full_send_sync(
                 request,
                 self.o_a_rsmd.as_ref(),
                 Some(&self.a_descriptors),
                 &mut None,
             )?;
-
-            // if the input was not transferred completely in the same roundtrip,
-            // then the statement execution roundtrip cannot bring any of the expected results;
-            // instead, the results that belong to the procedure execution roundtrip
-            // will be received with the response to the last input-LOB transfer-roundtrip.
-            let write_lob_reply = main_reply
-                .parts
-                .remove_first_of_kind(PartKind::WriteLobReply);
-
-            let (mut internal_return_values, replytype) = (
-                main_reply
-                    .parts
-                    .into_internal_return_values(&mut ps_core_guard.am_conn_core, None)?,
-                main_reply.replytype,
-            );
-
-            if let Some(Part::WriteLobReply(wlr)) = write_lob_reply {
-                let locator_ids = wlr.into_locator_ids();
-                if locator_ids.len() != readers.len() {
-                    return Err(HdbError::UsageDetailed(format!(
-                        "The number of provided readers ({}) does not match \
-                         the number of required readers ({})",
-                        readers.len(),
-                        locator_ids.len()
-                    )));
-                }
-                for (locator_id, (reader, type_id)) in locator_ids.into_iter().zip(readers) {
-                    debug!("writing content to locator with id {:?}", locator_id);
-                    if let HdbValue::LOBSTREAM(Some(reader)) = reader {
-                        let mut reader = reader.lock()?;
-                        let mut writer = LobWriter::new(
-                            locator_id,
-                            type_id,
-                            ps_core_guard.am_conn_core.clone(),
-                            self.o_a_rsmd.as_ref(),
-                            Some(&self.a_descriptors),
-                        )?;
-                        std::io::copy(&mut *reader, &mut writer).map_err(HdbError::LobStreaming)?;
-                        writer.flush().map_err(HdbError::LobStreaming)?;
-                        if let Some(mut irvs) = writer.into_internal_return_values() {
-                            internal_return_values.append(&mut irvs);
-                        }
+            let (locator_ids, mut internal_return_values, replytype) =
+                Self::digest_execute_row_reply(main_reply, &mut am_conn_core, readers.len())?;
+
+            for (locator_id, (reader, type_id)) in locator_ids.into_iter().zip(readers) {
+                debug!("writing content to locator with id {:?}", locator_id);
+                if let HdbValue::LOBSTREAM(Some(reader)) = reader {
+                    let mut reader = reader.lock()?;
+                    let mut writer = LobWriter::new(
+                        locator_id,
+                        type_id,
+                        am_conn_core.clone(),
+                        self.o_a_rsmd.as_ref(),
+                        Some(&self.a_descriptors),
+                    )?;
+                    std::io::copy(&mut *reader, &mut writer).map_err(HdbError::LobStreaming)?;
+                    writer.flush().map_err(HdbError::LobStreaming)?;
+                    if let Some(mut irvs) = writer.into_internal_return_values() {
+                        internal_return_values.append(&mut irvs);
                     }
                 }
             }
 
-            // inject statement id
-            for rv in &mut internal_return_values {
-                if let InternalReturnValue::ResultSet(rs) = rv {
-                    rs.inject_statement_id(Arc::clone(&self.am_ps_core))?;
-                }
-            }
+            self.inject_statement_id(&mut internal_return_values)?;
             HdbResponse::try_new(internal_return_values, replytype)
         } else {
             self.execute_parameter_rows(None)
@@ -344,12 +302,8 @@ impl<'a> PreparedStatement {
     ///
     /// Several variants of `HdbError` can occur.
     pub fn execute_batch(&mut self) -> HdbResult<HdbResponse> {
-        if self.batch.is_empty() && self.a_descriptors.has_in() {
-            return Err(HdbError::Usage("Empty batch cannot be executed"));
-        }
-        let mut rows2 = ParameterRows::new();
-        std::mem::swap(&mut self.batch, &mut rows2);
-        self.execute_parameter_rows(Some(rows2))
+        let rows = self.take_batch()?;
+        self.execute_parameter_rows(Some(rows))
     }
 
     /// Descriptors of all parameters of the prepared statement (in, out, inout).
@@ -359,32 +313,188 @@ impl<'a> PreparedStatement {
 
     fn execute_parameter_rows(&mut self, o_rows: Option<ParameterRows>) -> HdbResult<HdbResponse> {
         trace!("PreparedStatement::execute_parameter_rows()");
+        self.check_routing()?;
+        let (mut am_conn_core, request) = self.execute_request(o_rows)?;
+        let reply = am_conn_core.full_send_sync(
+            request,
+            self.o_a_rsmd.as_ref(),
+            Some(&self.a_descriptors),
+            &mut None,
+        )?;
+        self.response_from_reply(reply, &mut am_conn_core)
+    }
+
+    // Converts the input into a row of parameters, if the statement has input parameters.
+    pub(crate) fn parameter_rows_from<T: serde::ser::Serialize>(
+        &self,
+        input: &T,
+    ) -> HdbResult<Option<ParameterRows<'static>>> {
+        if self.a_descriptors.has_in() {
+            let mut par_rows = ParameterRows::new();
+            par_rows.push(input, &self.a_descriptors)?;
+            Ok(Some(par_rows))
+        } else {
+            Ok(None)
+        }
+    }
 
-        let mut ps_core_guard = self.am_ps_core.lock()?;
+    pub(crate) fn take_batch(&mut self) -> HdbResult<ParameterRows<'static>> {
+        if self.batch.is_empty() && self.a_descriptors.has_in() {
+            return Err(HdbError::Usage("Empty batch cannot be executed"));
+        }
+        let mut rows2 = ParameterRows::new();
+        std::mem::swap(&mut self.batch, &mut rows2);
+        Ok(rows2)
+    }
+
+    pub(crate) fn execute_request<'b>(
+        &self,
+        o_rows: Option<ParameterRows<'b>>,
+    ) -> HdbResult<(AmConnCore, Request<'b>)> {
+        let ps_core_guard = self.am_ps_core.lock()?;
         let mut request = Request::new(RequestType::Execute, HOLD_CURSORS_OVER_COMMIT);
+        if let Some(query_timeout) = self.o_query_timeout {
+            request.set_query_timeout(query_timeout);
+        }
         request.push(Part::StatementId(ps_core_guard.statement_id));
         if let Some(rows) = o_rows {
             request.push(Part::Parameters(rows));
         }
+        Ok((ps_core_guard.am_conn_core.clone(), request))
+    }
 
-        let (mut internal_return_values, replytype) = ps_core_guard
-            .am_conn_core
-            .full_send_sync(
-                request,
-                self.o_a_rsmd.as_ref(),
-                Some(&self.a_descriptors),
-                &mut None,
-            )?
-            .into_internal_return_values(&mut ps_core_guard.am_conn_core, None)?;
+    pub(crate) fn response_from_reply(
+        &self,
+        reply: Reply,
+        am_conn_core: &mut AmConnCore,
+    ) -> HdbResult<HdbResponse> {
+        let (mut internal_return_values, replytype) =
+            reply.into_internal_return_values(am_conn_core, None)?;
+        self.inject_statement_id(&mut internal_return_values)?;
+        HdbResponse::try_new(internal_return_values, replytype)
+    }
+
+    // Builds the request for execute_row();
+    // if readers were provided, they are picked out and replaced with None.
+    #[allow(clippy::type_complexity)]
+    pub(crate) fn execute_row_request<'b>(
+        &self,
+        hdb_values: Vec<HdbValue<'b>>,
+    ) -> HdbResult<(AmConnCore, Request<'b>, Vec<(HdbValue<'b>, TypeId)>)> {
+        let am_conn_core = self.am_ps_core.lock()?.am_conn_core.clone();
+        let mut request = Request::new(RequestType::Execute, HOLD_CURSORS_OVER_COMMIT);
+        if let Some(query_timeout) = self.o_query_timeout {
+            request.set_query_timeout(query_timeout);
+        }
+        request.push(Part::StatementId(self.am_ps_core.lock()?.statement_id));
+
+        let mut readers: Vec<(HdbValue, TypeId)> = vec![];
+        let hdb_values = hdb_values
+            .into_iter()
+            .zip(self.a_descriptors.iter_in())
+            .map(|(v, d)| {
+                if let HdbValue::LOBSTREAM(Some(_)) = v {
+                    readers.push((v, d.type_id()));
+                    HdbValue::LOBSTREAM(None)
+                } else {
+                    v
+                }
+            })
+            .collect();
+
+        let mut par_rows = ParameterRows::new();
+        par_rows.push_hdb_values(hdb_values, &self.a_descriptors)?;
+        request.push(Part::Parameters(par_rows));
+
+        if am_conn_core
+            .lock()?
+            .connect_options()
+            .get_implicit_lob_streaming()
+            .unwrap_or(false)
+        {
+            request.push(Part::LobFlags(LobFlags::for_implicit_streaming()));
+        }
+        Ok((am_conn_core, request, readers))
+    }
+
+    // If the input was not transferred completely in the same roundtrip,
+    // then the statement execution roundtrip cannot bring any of the expected results;
+    // instead, the results that belong to the procedure execution roundtrip
+    // will be received with the response to the last input-LOB transfer-roundtrip.
+    //
+    // Returns the locator ids to which the readers' content needs to be written.
+    pub(crate) fn digest_execute_row_reply(
+        mut main_reply: Reply,
+        am_conn_core: &mut AmConnCore,
+        no_of_readers: usize,
+    ) -> HdbResult<(Vec<u64>, Vec<InternalReturnValue>, ReplyType)> {
+        let write_lob_reply = main_reply
+            .parts
+            .remove_first_of_kind(PartKind::WriteLobReply);
+
+        let (internal_return_values, replytype) = (
+            main_reply
+                .parts
+                .into_internal_return_values(am_conn_core, None)?,
+            main_reply.replytype,
+        );
 
-        // inject statement id
-        for rv in &mut internal_return_values {
+        let locator_ids = if let Some(Part::WriteLobReply(wlr)) = write_lob_reply {
+            let locator_ids = wlr.into_locator_ids();
+            if locator_ids.len() != no_of_readers {
+                return Err(HdbError::UsageDetailed(format!(
+                    "The number of provided readers ({}) does not match \
+                     the number of required readers ({})",
+                    no_of_readers,
+                    locator_ids.len()
+                )));
+            }
+            locator_ids
+        } else {
+            Vec::new()
+        };
+        Ok((locator_ids, internal_return_values, replytype))
+    }
+
+    pub(crate) fn inject_statement_id(
+        &self,
+        internal_return_values: &mut Vec<InternalReturnValue>,
+    ) -> HdbResult<()> {
+        for rv in internal_return_values {
             if let InternalReturnValue::ResultSet(rs) = rv {
                 rs.inject_statement_id(Arc::clone(&self.am_ps_core))?;
             }
         }
+        Ok(())
+    }
 
-        HdbResponse::try_new(internal_return_values, replytype)
+    #[cfg(feature = "async")]
+    pub(crate) fn o_a_rsmd(&self) -> Option<&Arc<ResultSetMetadata>> {
+        self.o_a_rsmd.as_ref()
+    }
+
+    #[cfg(feature = "async")]
+    pub(crate) fn a_descriptors(&self) -> &Arc<ParameterDescriptors> {
+        &self.a_descriptors
+    }
+
+    /// Sets the query timeout for the executions of this statement,
+    /// overriding the query timeout of the connection
+    /// (see [`Connection::set_query_timeout()`](struct.Connection.html#method.set_query_timeout)).
+    ///
+    /// `Duration::ZERO` executes the statement without query timeout.
+    ///
+    /// # Errors
+    ///
+    /// `HdbError::Usage` if the database server does not support query timeouts.
+    pub fn set_query_timeout(&mut self, query_timeout: Duration) -> HdbResult<()> {
+        self.am_ps_core
+            .lock()?
+            .am_conn_core
+            .lock()?
+            .check_query_timeout_support()?;
+        self.o_query_timeout = Some(query_timeout);
+        Ok(())
     }
 
     /// Provides information about the the server-side resource consumption that
@@ -394,12 +504,152 @@ impl<'a> PreparedStatement {
     }
 
     // Prepare a statement.
+    //
+    // With statement routing, a statement is prepared on the primary connection to learn
+    // the table location; if the table is located on another host, the statement is prepared
+    // again on a secondary connection to that host, and is then executed there.
+    // The table location is remembered, so that further prepares of the same statement
+    // go to the secondary connection directly.
+    //
+    // The partition information is parsed, but not evaluated: assigning parameter values
+    // to hash partitions needs the server's partitioning function, which is not part of the
+    // published protocol. Statements on partitioned tables are thus routed to the first host
+    // of their table location, which forwards them to the owning partition where needed.
     pub(crate) fn try_new(mut am_conn_core: AmConnCore, stmt: &str) -> HdbResult<Self> {
+        let o_known_location = am_conn_core.lock()?.routed_table_location(stmt);
+        if let Some(table_location) = o_known_location {
+            if let Some(secondary) = am_conn_core.routing_target(&table_location)? {
+                let prepared_statement =
+                    Self::try_new_routed(am_conn_core.clone(), secondary, stmt)?;
+                if prepared_statement
+                    .o_table_location
+                    .as_ref()
+                    .is_some_and(|location| *location != table_location)
+                {
+                    am_conn_core.lock()?.set_routed_table_location(stmt, None);
+                }
+                return Ok(prepared_statement);
+            }
+        }
+
+        let reply = am_conn_core.send_sync(Self::prepare_request(stmt))?;
+        let prepared_statement = Self::from_prepare_reply(am_conn_core.clone(), reply)?;
+        if let Some(ref table_location) = prepared_statement.o_table_location {
+            if let Some(secondary) = am_conn_core.routing_target(table_location)? {
+                am_conn_core
+                    .lock()?
+                    .set_routed_table_location(stmt, Some(table_location));
+                return Self::try_new_routed(am_conn_core, secondary, stmt);
+            }
+        }
+        Ok(prepared_statement)
+    }
+
+    fn try_new_routed(
+        primary: AmConnCore,
+        mut secondary: AmConnCore,
+        stmt: &str,
+    ) -> HdbResult<Self> {
+        debug!(
+            "Routing statement to {}",
+            secondary.lock()?.connect_params().addr()
+        );
+        let reply = secondary.send_sync(Self::prepare_request(stmt))?;
+        Ok(Self::from_prepare_reply(secondary, reply)?.routed_from(primary, stmt, false))
+    }
+
+    // Marks the statement as prepared on another connection than the given primary one.
+    pub(crate) fn routed_from(mut self, primary: AmConnCore, stmt: &str, read_only: bool) -> Self {
+        self.o_routing = Some(Routing {
+            primary,
+            stmt: stmt.to_string(),
+            read_only,
+        });
+        self
+    }
+
+    // Routed statements are only executed in auto-commit mode, so that a transaction never
+    // spans several sessions; within a transaction, the statement is prepared again
+    // on the primary connection and executed there.
+    //
+    // Read-only statements are executed on the secondary site in any case, unless the
+    // companion connection became unusable; execute() then also repeats a failed
+    // execution on the primary connection.
+    // Before a routed execution, the secondary session follows the current schema
+    // and the isolation level of the primary connection.
+    fn check_routing(&mut self) -> HdbResult<()> {
+        if self.is_on_broken_companion()? {
+            debug!("Companion connection for read-only routing is broken");
+            return self.prepare_on_primary();
+        }
+        let in_transaction = match self.o_routing {
+            Some(ref routing) => {
+                let primary = routing.primary.lock()?;
+                if routing.read_only || primary.is_auto_commit() {
+                    let secondary = self.am_ps_core.lock()?.am_conn_core.clone();
+                    primary.align_session_of(&secondary)?;
+                    false
+                } else {
+                    true
+                }
+            }
+            None => false,
+        };
+        if in_transaction {
+            self.prepare_on_primary()?;
+        }
+        Ok(())
+    }
+
+    // Whether this is a read-only statement whose companion connection is broken.
+    fn is_on_broken_companion(&self) -> HdbResult<bool> {
+        if self
+            .o_routing
+            .as_ref()
+            .is_some_and(|routing| routing.read_only)
+        {
+            Ok(self.am_ps_core.lock()?.am_conn_core.lock()?.is_broken())
+        } else {
+            Ok(false)
+        }
+    }
+
+    // Replaces the routed statement with one that is prepared on the primary connection.
+    fn prepare_on_primary(&mut self) -> HdbResult<()> {
+        if let Some(routing) = self.o_routing.take() {
+            debug!("Preparing the routed statement on the primary connection");
+            let mut primary = routing.primary;
+            let reply = primary.send_sync(Self::prepare_request(&routing.stmt))?;
+            let prepared_statement = Self::from_prepare_reply(primary, reply)?;
+            self.am_ps_core = prepared_statement.am_ps_core;
+            self.a_descriptors = prepared_statement.a_descriptors;
+            self.o_a_rsmd = prepared_statement.o_a_rsmd;
+            self.o_table_location = prepared_statement.o_table_location;
+        }
+        Ok(())
+    }
+
+    // A further handle to the same server-side statement, with an empty batch.
+    pub(crate) fn share(&self) -> Self {
+        Self {
+            am_ps_core: Arc::clone(&self.am_ps_core),
+            server_usage: ServerUsage::default(),
+            batch: ParameterRows::new(),
+            a_descriptors: Arc::clone(&self.a_descriptors),
+            o_a_rsmd: self.o_a_rsmd.clone(),
+            o_table_location: self.o_table_location.clone(),
+            o_query_timeout: None,
+            o_routing: self.o_routing.clone(),
+        }
+    }
+
+    pub(crate) fn prepare_request(stmt: &str) -> Request<'_> {
         let mut request = Request::new(RequestType::Prepare, HOLD_CURSORS_OVER_COMMIT);
         request.push(Part::Command(stmt));
+        request
+    }
 
-        let reply = am_conn_core.send_sync(request)?;
-
+    pub(crate) fn from_prepare_reply(am_conn_core: AmConnCore, reply: Reply) -> HdbResult<Self> {
         let mut o_table_location: Option<Vec<i32>> = None;
         let mut o_stmt_id: Option<u64> = None;
         let mut a_descriptors: Arc<ParameterDescriptors> = Arc::new(ParameterDescriptors::new());
@@ -421,6 +671,10 @@ impl<'a> PreparedStatement {
                 Part::TableLocation(vec_i) => {
                     o_table_location = Some(vec_i);
                 }
+                Part::PartitionInformation(partition_information) => {
+                    // routing per partition is not supported, see try_new()
+                    trace!("Partition information: {:?}", partition_information);
+                }
                 Part::ResultSetMetadata(rs_md) => {
                     o_a_rsmd = Some(Arc::new(rs_md));
                 }
@@ -439,9 +693,11 @@ impl<'a> PreparedStatement {
         }
 
         let statement_id = o_stmt_id.ok_or_else(|| HdbError::Impl("No StatementId received"))?;
+        let session_generation = am_conn_core.lock()?.session_generation();
         let am_ps_core = Arc::new(Mutex::new(PreparedStatementCore {
             am_conn_core,
             statement_id,
+            session_generation,
         }));
         debug!(
             "PreparedStatement created with parameter descriptors = {:?}",
@@ -453,7 +709,9 @@ impl<'a> PreparedStatement {
             batch: ParameterRows::new(),
             a_descriptors,
             o_a_rsmd,
-            _o_table_location: o_table_location,
+            o_table_location,
+            o_query_timeout: None,
+            o_routing: None,
         })
     }
 }
@@ -461,10 +719,15 @@ impl<'a> PreparedStatement {
 impl Drop for PreparedStatementCore {
     /// Frees all server-side resources that belong to this prepared statement.
     fn drop(&mut self) {
-        let mut request = Request::new(RequestType::DropStatementId, 0);
-        request.push(Part::StatementId(self.statement_id));
-        if let Ok(mut reply) = self.am_conn_core.send_sync(request) {
-            reply.parts.pop_if_kind(PartKind::StatementContext);
+        // after a reconnect, the statement is already gone
+        let is_alive = self
+            .am_conn_core
+            .lock()
+            .is_ok_and(|conn_core| conn_core.session_generation() == self.session_generation);
+        if is_alive {
+            let mut request = Request::new(RequestType::DropStatementId, 0);
+            request.push(Part::StatementId(self.statement_id));
+            self.am_conn_core.send_and_ignore_errors(request);
         }
     }
 }
@@ -1,4 +1,6 @@
 use super::fetch::fetch_a_lob_chunk;
+#[cfg(feature = "async")]
+use super::fetch::fetch_a_lob_chunk_async;
 use crate::conn::AmConnCore;
 use crate::protocol::parts::AmRsCore;
 use crate::protocol::ServerUsage;
@@ -100,6 +102,42 @@ impl BLob {
         self.0.read_slice(offset, length)
     }
 
+    /// Converts the `BLob` asynchronously, like [`into_bytes`](#method.into_bytes) does it
+    /// for `BLob`s that were received over a synchronous `Connection`.
+    ///
+    /// # Errors
+    ///
+    /// Several variants of `HdbError` can occur.
+    #[cfg(feature = "async")]
+    pub async fn into_bytes_async(self) -> HdbResult<Vec<u8>> {
+        trace!("BLob::into_bytes_async()");
+        self.0.into_bytes_async().await
+    }
+
+    /// Reads asynchronously from given offset and the given length, in bytes.
+    ///
+    /// # Errors
+    ///
+    /// Several variants of `HdbError` can occur.
+    #[cfg(feature = "async")]
+    pub async fn read_slice_async(&mut self, offset: u64, length: u32) -> HdbResult<Vec<u8>> {
+        self.0.read_slice_async(offset, length).await
+    }
+
+    /// Reads asynchronously into the given buffer and returns the number of bytes
+    /// that were read, or 0 if all data have been read.
+    ///
+    /// This is the streaming counterpart of the `Read` implementation for `BLob`s
+    /// that were received over an `AsyncConnection`.
+    ///
+    /// # Errors
+    ///
+    /// Several variants of `HdbError` can occur.
+    #[cfg(feature = "async")]
+    pub async fn read_async(&mut self, buf: &mut [u8]) -> HdbResult<usize> {
+        self.0.read_async(buf).await
+    }
+
     /// Total length of data, in bytes.
     pub fn total_byte_length(&self) -> u64 {
         self.0.total_byte_length()
@@ -201,8 +239,37 @@ impl BLobHandle {
         self.data.len()
     }
 
-    #[allow(clippy::cast_possible_truncation)]
     fn fetch_next_chunk(&mut self) -> HdbResult<()> {
+        let (offset, read_length) = self.next_chunk_position()?;
+        let (reply_data, reply_is_last_data) = fetch_a_lob_chunk(
+            &mut self.am_conn_core,
+            self.locator_id,
+            offset,
+            read_length,
+            &mut self.server_usage,
+        )?;
+        self.digest_chunk(reply_data, reply_is_last_data);
+        Ok(())
+    }
+
+    #[cfg(feature = "async")]
+    async fn fetch_next_chunk_async(&mut self) -> HdbResult<()> {
+        let (offset, read_length) = self.next_chunk_position()?;
+        let (reply_data, reply_is_last_data) = fetch_a_lob_chunk_async(
+            &self.am_conn_core,
+            self.locator_id,
+            offset,
+            read_length,
+            &mut self.server_usage,
+        )
+        .await?;
+        self.digest_chunk(reply_data, reply_is_last_data);
+        Ok(())
+    }
+
+    // Returns the offset and the length of the next chunk that needs to be fetched.
+    #[allow(clippy::cast_possible_truncation)]
+    fn next_chunk_position(&self) -> HdbResult<(u64, u32)> {
         if self.is_data_complete {
             return Err(HdbError::Impl("fetch_next_chunk(): already complete"));
         }
@@ -211,15 +278,10 @@ impl BLobHandle {
             self.am_conn_core.lock()?.get_lob_read_length() as u32,
             (self.total_byte_length - self.acc_byte_length as u64) as u32,
         );
+        Ok((self.acc_byte_length as u64, read_length))
+    }
 
-        let (reply_data, reply_is_last_data) = fetch_a_lob_chunk(
-            &mut self.am_conn_core,
-            self.locator_id,
-            self.acc_byte_length as u64,
-            read_length,
-            &mut self.server_usage,
-        )?;
-
+    fn digest_chunk(&mut self, reply_data: Vec<u8>, reply_is_last_data: bool) {
         self.acc_byte_length += reply_data.len();
         self.data.append(&mut VecDeque::from(reply_data));
         self.is_data_complete = reply_is_last_data;
@@ -234,7 +296,6 @@ impl BLobHandle {
             self.is_data_complete,
             self.data.len()
         );
-        Ok(())
     }
 
     fn load_complete(&mut self) -> HdbResult<()> {
@@ -245,6 +306,46 @@ impl BLobHandle {
         Ok(())
     }
 
+    #[cfg(feature = "async")]
+    async fn read_slice_async(&mut self, offset: u64, length: u32) -> HdbResult<Vec<u8>> {
+        let (reply_data, _reply_is_last_data) = fetch_a_lob_chunk_async(
+            &self.am_conn_core,
+            self.locator_id,
+            offset,
+            length,
+            &mut self.server_usage,
+        )
+        .await?;
+        debug!("read_slice_async(): got {} bytes", reply_data.len());
+        Ok(reply_data)
+    }
+
+    #[cfg(feature = "async")]
+    async fn load_complete_async(&mut self) -> HdbResult<()> {
+        trace!("load_complete_async()");
+        while !self.is_data_complete {
+            self.fetch_next_chunk_async().await?;
+        }
+        Ok(())
+    }
+
+    #[cfg(feature = "async")]
+    async fn into_bytes_async(mut self) -> HdbResult<Vec<u8>> {
+        trace!("into_bytes_async()");
+        self.load_complete_async().await?;
+        Ok(Vec::from(self.data))
+    }
+
+    // Fetches what is needed to fill the buffer, then reads from the internal buffer
+    // without further roundtrips.
+    #[cfg(feature = "async")]
+    async fn read_async(&mut self, buf: &mut [u8]) -> HdbResult<usize> {
+        while !self.is_data_complete && (buf.len() > self.data.len()) {
+            self.fetch_next_chunk_async().await?;
+        }
+        std::io::Read::read(self, buf).map_err(HdbError::LobStreaming)
+    }
+
     fn max_buf_len(&self) -> usize {
         self.max_buf_len
     }
@@ -1,6 +1,6 @@
 use crate::conn::AmConnCore;
 use crate::protocol::parts::{ReadLobReply, ReadLobRequest};
-use crate::protocol::{Part, ReplyType, Request, RequestType, ServerUsage};
+use crate::protocol::{Part, Reply, ReplyType, Request, RequestType, ServerUsage};
 use crate::{HdbError, HdbResult};
 
 // Note that requested_length and offset count either bytes (BLOB, CLOB), or 1-2-3-chars (NCLOB)
@@ -11,13 +11,38 @@ pub(crate) fn fetch_a_lob_chunk(
     length: u32,
     server_usage: &mut ServerUsage,
 ) -> HdbResult<(Vec<u8>, bool)> {
+    let request = read_lob_request(locator_id, offset, length);
+    let reply = am_conn_core.send_sync(request)?;
+    evaluate_read_lob_reply(reply, locator_id, server_usage)
+}
+
+#[cfg(feature = "async")]
+pub(crate) async fn fetch_a_lob_chunk_async(
+    am_conn_core: &AmConnCore,
+    locator_id: u64,
+    offset: u64,
+    length: u32,
+    server_usage: &mut ServerUsage,
+) -> HdbResult<(Vec<u8>, bool)> {
+    let request = read_lob_request(locator_id, offset, length);
+    let reply = am_conn_core.send_async(request).await?;
+    evaluate_read_lob_reply(reply, locator_id, server_usage)
+}
+
+fn read_lob_request(locator_id: u64, offset: u64, length: u32) -> Request<'static> {
     let mut request = Request::new(RequestType::ReadLob, 0);
     let offset = offset + 1;
     request.push(Part::ReadLobRequest(ReadLobRequest::new(
         locator_id, offset, length,
     )));
+    request
+}
 
-    let reply = am_conn_core.send_sync(request)?;
+fn evaluate_read_lob_reply(
+    reply: Reply,
+    locator_id: u64,
+    server_usage: &mut ServerUsage,
+) -> HdbResult<(Vec<u8>, bool)> {
     reply.assert_expected_reply_type(ReplyType::ReadLob)?;
 
     let mut o_read_lob_reply = None;
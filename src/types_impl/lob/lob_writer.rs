@@ -54,6 +54,54 @@ impl<'a> LobWriter<'a> {
     // Note that requested_length and offset count either bytes (for BLOB, CLOB),
     // or 1-2-3-chars (for NCLOB)
     fn write_a_lob_chunk(&mut self, lob_write_mode: &LobWriteMode) -> HdbResult<Vec<u64>> {
+        let request = self.write_lob_request(lob_write_mode);
+        let reply = self.am_conn_core.full_send_sync(
+            request,
+            self.o_a_rsmd,
+            self.o_a_descriptors,
+            &mut None,
+        )?;
+        self.evaluate_reply(reply)
+    }
+
+    #[cfg(feature = "async")]
+    async fn write_a_lob_chunk_async(
+        &mut self,
+        lob_write_mode: &LobWriteMode<'_>,
+    ) -> HdbResult<Vec<u64>> {
+        let request = self.write_lob_request(lob_write_mode);
+        let reply = self
+            .am_conn_core
+            .full_send_async(request, self.o_a_rsmd, self.o_a_descriptors, None)
+            .await?;
+        self.evaluate_reply(reply)
+    }
+
+    // Counterpart of `Write::write()` for non-blocking connections.
+    #[cfg(feature = "async")]
+    pub async fn write_async(&mut self, input: &[u8]) -> HdbResult<()> {
+        trace!("write_async() with input of len {}", input.len());
+        if let Some(payload) = self
+            .payload_for_append(input)
+            .map_err(HdbError::LobStreaming)?
+        {
+            self.write_a_lob_chunk_async(&LobWriteMode::Append(&payload))
+                .await?;
+        }
+        Ok(())
+    }
+
+    // Counterpart of `Write::flush()` for non-blocking connections.
+    #[cfg(feature = "async")]
+    pub async fn flush_async(&mut self) -> HdbResult<()> {
+        trace!("flush_async(), with buffer of {} bytes", self.buffer.len());
+        let payload = self.payload_for_last().map_err(HdbError::LobStreaming)?;
+        self.write_a_lob_chunk_async(&LobWriteMode::Last(&payload))
+            .await?;
+        Ok(())
+    }
+
+    fn write_lob_request<'b>(&self, lob_write_mode: &LobWriteMode<'b>) -> Request<'b> {
         let mut request = Request::new(RequestType::WriteLob, 0);
         let write_lob_request = match lob_write_mode {
             // LobWriteMode::Offset(offset, buf) =>
@@ -62,14 +110,10 @@ impl<'a> LobWriter<'a> {
             LobWriteMode::Last(buf) => WriteLobRequest::new(self.locator_id, -1_i64, buf, true),
         };
         request.push(Part::WriteLobRequest(write_lob_request));
+        request
+    }
 
-        let reply = self.am_conn_core.full_send_sync(
-            request,
-            self.o_a_rsmd,
-            self.o_a_descriptors,
-            &mut None,
-        )?;
-
+    fn evaluate_reply(&mut self, reply: Reply) -> HdbResult<Vec<u64>> {
         match reply.replytype {
             // regular response
             ReplyType::WriteLob => self.evaluate_write_lob_reply(reply),
@@ -84,6 +128,54 @@ impl<'a> LobWriter<'a> {
         }
     }
 
+    // Either buffers the input (and returns None) or returns buffer + input as payload
+    fn payload_for_append(&mut self, input: &[u8]) -> std::io::Result<Option<Vec<u8>>> {
+        if input.len() + self.buffer.len() < self.lob_write_length {
+            self.buffer.append(&mut input.to_vec());
+            Ok(None)
+        } else {
+            // concatenate buffer and input into payload_raw
+            let payload_raw = if self.buffer.is_empty() {
+                input.to_vec()
+            } else {
+                let mut payload_raw = Vec::<u8>::new();
+                std::mem::swap(&mut payload_raw, &mut self.buffer);
+                payload_raw.append(&mut input.to_vec());
+                payload_raw
+            };
+            debug_assert!(self.buffer.is_empty());
+
+            // if necessary, cut off new tail and convert to cesu8
+            let payload = if let TypeId::CLOB | TypeId::NCLOB = self.type_id {
+                let (cesu8, utf8_tail) = utf8_to_cesu8_and_utf8_tail(payload_raw)?;
+                self.buffer = utf8_tail;
+                cesu8
+            } else {
+                payload_raw
+            };
+            Ok(Some(payload))
+        }
+    }
+
+    // Returns the buffer as final payload
+    fn payload_for_last(&mut self) -> std::io::Result<Vec<u8>> {
+        let mut payload_raw = Vec::<u8>::new();
+        std::mem::swap(&mut payload_raw, &mut self.buffer);
+        if let TypeId::CLOB | TypeId::NCLOB = self.type_id {
+            let (cesu8, utf8_tail) = utf8_to_cesu8_and_utf8_tail(payload_raw)
+                .map_err(|e| util::io_error(e.to_string()))?;
+            if !utf8_tail.is_empty() {
+                return Err(std::io::Error::new(
+                    std::io::ErrorKind::Other,
+                    "stream is ending with invalid utf-8",
+                ));
+            }
+            Ok(cesu8)
+        } else {
+            Ok(payload_raw)
+        }
+    }
+
     fn evaluate_write_lob_reply(&mut self, reply: Reply) -> HdbResult<Vec<u64>> {
         let mut result = None;
 
@@ -166,29 +258,7 @@ impl<'a> Write for LobWriter<'a> {
     // Either buffers (in self.buffer) or writes buffer + input to the db
     fn write(&mut self, input: &[u8]) -> std::io::Result<usize> {
         trace!("write() with input of len {}", input.len());
-        if input.len() + self.buffer.len() < self.lob_write_length {
-            self.buffer.append(&mut input.to_vec());
-        } else {
-            // concatenate buffer and input into payload_raw
-            let payload_raw = if self.buffer.is_empty() {
-                input.to_vec()
-            } else {
-                let mut payload_raw = Vec::<u8>::new();
-                std::mem::swap(&mut payload_raw, &mut self.buffer);
-                payload_raw.append(&mut input.to_vec());
-                payload_raw
-            };
-            debug_assert!(self.buffer.is_empty());
-
-            // if necessary, cut off new tail and convert to cesu8
-            let payload = if let TypeId::CLOB | TypeId::NCLOB = self.type_id {
-                let (cesu8, utf8_tail) = utf8_to_cesu8_and_utf8_tail(payload_raw)?;
-                self.buffer = utf8_tail;
-                cesu8
-            } else {
-                payload_raw
-            };
-
+        if let Some(payload) = self.payload_for_append(input)? {
             self.write_a_lob_chunk(&LobWriteMode::Append(&payload))
                 .map(|_locator_ids| ())
                 .map_err(|e| util::io_error(e.to_string()))?;
@@ -198,22 +268,7 @@ impl<'a> Write for LobWriter<'a> {
 
     fn flush(&mut self) -> std::io::Result<()> {
         trace!("flush(), with buffer of {} bytes", self.buffer.len());
-        let mut payload_raw = Vec::<u8>::new();
-        std::mem::swap(&mut payload_raw, &mut self.buffer);
-        let payload = if let TypeId::CLOB | TypeId::NCLOB = self.type_id {
-            let (cesu8, utf8_tail) = utf8_to_cesu8_and_utf8_tail(payload_raw)
-                .map_err(|e| util::io_error(e.to_string()))?;
-            if !utf8_tail.is_empty() {
-                return Err(std::io::Error::new(
-                    std::io::ErrorKind::Other,
-                    "stream is ending with invalid utf-8",
-                ));
-            }
-            cesu8
-        } else {
-            payload_raw
-        };
-
+        let payload = self.payload_for_last()?;
         self.write_a_lob_chunk(&LobWriteMode::Last(&payload))
             .map(|_locator_ids| ())
             .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e.to_string()))?;
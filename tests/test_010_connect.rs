@@ -22,6 +22,11 @@ pub fn test_010_connect() -> HdbResult<()> {
     connect_and_select_with_explicit_clientlocale(&mut log_handle)?;
     connect_and_select_with_clientlocale_from_env(&mut log_handle)?;
     command_info(&mut log_handle)?;
+    connect_with_statement_routing(&mut log_handle)?;
+    topology(&mut log_handle)?;
+    connect_with_database_name(&mut log_handle)?;
+    session_variables(&mut log_handle)?;
+    connect_with_read_only_routing(&mut log_handle)?;
     info!("Elapsed time: {:?}", Instant::now().duration_since(start));
     Ok(())
 }
@@ -218,3 +223,122 @@ fn command_info(_log_handle: &mut ReconfigurationHandle) -> HdbResult<()> {
 
     Ok(())
 }
+
+fn connect_with_statement_routing(_log_handle: &mut ReconfigurationHandle) -> HdbResult<()> {
+    info!("prepared statements are routed to the host that owns the table");
+    let mut setup_connection = test_utils::get_authenticated_connection()?;
+    setup_connection.multiple_statements_ignore_err(vec!["drop table TEST_ROUTING"]);
+    setup_connection.multiple_statements(vec![
+        "create table TEST_ROUTING (F1_I INT)",
+        "insert into TEST_ROUTING (F1_I) values(1)",
+    ])?;
+    let table_volume_id: i32 = setup_connection
+        .query(
+            "select volume_id from m_table_locations \
+             where schema_name = current_schema and table_name = 'TEST_ROUTING'",
+        )?
+        .try_into()?;
+
+    let mut cp_builder = test_utils::get_std_cp_builder()?;
+    cp_builder.statement_routing(true);
+    let mut connection = Connection::new(cp_builder)?;
+    let connection_id = connection.id()?;
+    let is_remote_table = connection
+        .topology()?
+        .iter()
+        .any(|host| host.is_current_session() && host.volume_id() != Some(table_volume_id));
+    debug!("TEST_ROUTING is remote: {}", is_remote_table);
+
+    // with auto-commit, a statement on a remote table is executed on a secondary connection
+    let mut stmt =
+        connection.prepare("select current_connection from TEST_ROUTING where F1_I = ?")?;
+    let executing_connection_id: i32 = stmt.execute(&1)?.into_resultset()?.try_into()?;
+    assert_eq!(is_remote_table, executing_connection_id != connection_id);
+
+    // the remembered table location is used when the statement is prepared again
+    let mut stmt =
+        connection.prepare("select current_connection from TEST_ROUTING where F1_I = ?")?;
+    let executing_connection_id: i32 = stmt.execute(&1)?.into_resultset()?.try_into()?;
+    assert_eq!(is_remote_table, executing_connection_id != connection_id);
+
+    // within a transaction, everything is executed on the primary connection
+    connection.set_auto_commit(false)?;
+    let executing_connection_id: i32 = stmt.execute(&1)?.into_resultset()?.try_into()?;
+    assert_eq!(executing_connection_id, connection_id);
+    connection.commit()?;
+
+    setup_connection.multiple_statements(vec!["drop table TEST_ROUTING"])?;
+    Ok(())
+}
+
+fn topology(_log_handle: &mut ReconfigurationHandle) -> HdbResult<()> {
+    info!("the topology lists the host of the current session");
+    let connection = test_utils::get_authenticated_connection()?;
+    let hosts = connection.topology()?;
+    debug!("Topology: {:?}", hosts);
+    assert!(!hosts.is_empty());
+    assert_eq!(
+        hosts
+            .iter()
+            .filter(|host| host.is_current_session())
+            .count(),
+        1
+    );
+    Ok(())
+}
+
+fn connect_with_database_name(_log_handle: &mut ReconfigurationHandle) -> HdbResult<()> {
+    info!("the database can be looked up by its name");
+    let mut connection = test_utils::get_authenticated_connection()?;
+    let database_name: String = connection
+        .query("select database_name from m_database")?
+        .try_into()?;
+
+    let mut cp_builder = test_utils::get_std_cp_builder()?;
+    cp_builder.database_name(&database_name);
+    let mut connection = Connection::new(cp_builder)?;
+    let database_name2: String = connection
+        .query("select database_name from m_database")?
+        .try_into()?;
+    assert_eq!(database_name, database_name2);
+    Ok(())
+}
+
+fn session_variables(_log_handle: &mut ReconfigurationHandle) -> HdbResult<()> {
+    info!("session variables are sent with the next request");
+    let mut connection = test_utils::get_authenticated_connection()?;
+    connection.set_session_variable("MY_VAR", "my value")?;
+    assert_eq!(
+        connection.get_session_variable("MY_VAR")?,
+        Some("my value".to_string())
+    );
+    let value: Option<String> = connection
+        .query("select session_context('MY_VAR') from dummy")?
+        .try_into()?;
+    assert_eq!(value, Some("my value".to_string()));
+
+    connection.unset_session_variable("MY_VAR")?;
+    assert_eq!(connection.get_session_variable("MY_VAR")?, None);
+    let value: Option<String> = connection
+        .query("select session_context('MY_VAR') from dummy")?
+        .try_into()?;
+    assert_eq!(value, None);
+    Ok(())
+}
+
+fn connect_with_read_only_routing(_log_handle: &mut ReconfigurationHandle) -> HdbResult<()> {
+    info!("read-only statements work with read-only routing, also without a secondary site");
+    let mut cp_builder = test_utils::get_std_cp_builder()?;
+    cp_builder.read_only_routing(true);
+    let mut connection = Connection::new(cp_builder)?;
+
+    let count: u32 = connection
+        .query_read_only("select count(*) from dummy")?
+        .try_into()?;
+    assert_eq!(count, 1);
+
+    let mut stmt = connection.prepare_read_only("select count(*) from dummy where dummy = ?")?;
+    let count: u32 = stmt.execute(&"X")?.into_resultset()?.try_into()?;
+    assert_eq!(count, 1);
+    Ok(())
+}
@@ -0,0 +1,49 @@
+#[macro_use]
+extern crate serde;
+
+mod test_utils;
+
+use hdbconnect::{Connection, HdbResult};
+use log::*;
+use std::time::Instant;
+
+#[test] // cargo test --test test_013_compression -- --nocapture
+pub fn test_013_compression() -> HdbResult<()> {
+    let _log_handle = test_utils::init_logger();
+    let start = Instant::now();
+
+    let mut cp_builder = test_utils::get_std_cp_builder()?;
+    cp_builder.compression(true);
+    let mut connection = Connection::new(cp_builder)?;
+    info!("TESTING WITH {}", connection.connect_string()?);
+
+    big_request_and_reply(&mut connection)?;
+
+    test_utils::closing_info(connection, start)
+}
+
+fn big_request_and_reply(connection: &mut Connection) -> HdbResult<()> {
+    info!("send and receive messages that are big enough to be compressed");
+    connection
+        .statement("drop table TEST_COMPRESSION")
+        .map(|_| ())
+        .unwrap_or_else(|e| debug!("Error intentionally ignored: {:?}", e));
+    connection.statement("create table TEST_COMPRESSION (F1_I INT, F2_S NVARCHAR(500))")?;
+
+    let text = "This text is repeated quite often, so it compresses well. ".repeat(8);
+    let mut insert_stmt = connection.prepare("insert into TEST_COMPRESSION values(?, ?)")?;
+    for i in 0..1000 {
+        insert_stmt.add_batch(&(i, &text))?;
+    }
+    insert_stmt.execute_batch()?;
+
+    let result: Vec<(i32, String)> = connection
+        .query("select F1_I, F2_S from TEST_COMPRESSION order by F1_I")?
+        .try_into()?;
+    assert_eq!(result.len(), 1000);
+    for (i, (f1, f2)) in result.into_iter().enumerate() {
+        assert_eq!(f1 as usize, i);
+        assert_eq!(f2, text);
+    }
+    Ok(())
+}
@@ -0,0 +1,127 @@
+#[macro_use]
+extern crate serde;
+
+mod test_utils;
+
+use hdbconnect::{ConnectParams, Connection, HdbError, HdbResult};
+use log::*;
+use std::time::{Duration, Instant};
+
+#[test] // cargo test --test test_014_timeouts -- --nocapture
+pub fn test_014_timeouts() -> HdbResult<()> {
+    let _log_handle = test_utils::init_logger();
+    let start = Instant::now();
+    read_timeout_with_silent_server()?;
+    read_timeout_with_slow_statement()?;
+    query_timeout_of_connection()?;
+    query_timeout_of_prepared_statement()?;
+    cancel_from_other_thread()?;
+    info!("Elapsed time: {:?}", Instant::now().duration_since(start));
+    Ok(())
+}
+
+// A server that accepts the tcp connection but never answers
+// must not let the client hang.
+fn read_timeout_with_silent_server() -> HdbResult<()> {
+    info!("connect to a server that never answers");
+    let listener = std::net::TcpListener::bind("127.0.0.1:0")?;
+    let port = listener.local_addr()?.port();
+
+    let params = ConnectParams::builder()
+        .hostname("127.0.0.1")
+        .port(port)
+        .dbuser("MEIER")
+        .password("schlau")
+        .connect_timeout(Duration::from_secs(5))
+        .read_timeout(Duration::from_millis(300))
+        .build()?;
+
+    let start = Instant::now();
+    match Connection::new(params) {
+        Err(HdbError::Timeout { .. }) => {}
+        Err(e) => panic!("expected HdbError::Timeout, got {:?}", e),
+        Ok(_) => panic!("connect to a silent server succeeded"),
+    }
+    assert!(Instant::now().duration_since(start) < Duration::from_secs(5));
+    drop(listener);
+    Ok(())
+}
+
+fn read_timeout_with_slow_statement() -> HdbResult<()> {
+    info!("run a statement that takes longer than the read timeout");
+    let mut cp_builder = test_utils::get_std_cp_builder()?;
+    cp_builder.read_timeout(Duration::from_secs(1));
+    let mut connection = Connection::new(cp_builder)?;
+
+    connection.query("select 1 from dummy")?;
+    match connection.exec("DO BEGIN CALL SQLSCRIPT_SYNC:SLEEP_SECONDS(3); END") {
+        Err(HdbError::Timeout { .. }) => Ok(()),
+        Err(e) => panic!("expected HdbError::Timeout, got {:?}", e),
+        Ok(_) => panic!("slow statement did not time out"),
+    }
+}
+
+fn query_timeout_of_connection() -> HdbResult<()> {
+    info!("the server aborts a statement that runs longer than the query timeout");
+    let mut connection = test_utils::get_authenticated_connection()?;
+    connection.set_query_timeout(Duration::from_secs(1))?;
+    assert_eq!(connection.query_timeout()?, Some(Duration::from_secs(1)));
+
+    match connection.exec("DO BEGIN CALL SQLSCRIPT_SYNC:SLEEP_SECONDS(3); END") {
+        Err(HdbError::QueryTimeout { .. }) => {}
+        Err(e) => panic!("expected HdbError::QueryTimeout, got {:?}", e),
+        Ok(_) => panic!("slow statement did not time out"),
+    }
+    // the connection is still usable
+    connection.query("select 1 from dummy")?;
+
+    connection.set_query_timeout(Duration::ZERO)?;
+    assert_eq!(connection.query_timeout()?, None);
+    connection.exec("DO BEGIN CALL SQLSCRIPT_SYNC:SLEEP_SECONDS(2); END")?;
+    Ok(())
+}
+
+fn query_timeout_of_prepared_statement() -> HdbResult<()> {
+    info!("a prepared statement can override the query timeout of the connection");
+    let mut connection = test_utils::get_authenticated_connection()?;
+    connection.set_query_timeout(Duration::from_secs(1))?;
+
+    let mut stmt = connection.prepare("DO BEGIN CALL SQLSCRIPT_SYNC:SLEEP_SECONDS(2); END")?;
+    match stmt.execute(&()) {
+        Err(HdbError::QueryTimeout { .. }) => {}
+        Err(e) => panic!("expected HdbError::QueryTimeout, got {:?}", e),
+        Ok(_) => panic!("slow statement did not time out"),
+    }
+    stmt.set_query_timeout(Duration::ZERO)?;
+    stmt.execute(&())?;
+    stmt.set_query_timeout(Duration::from_secs(5))?;
+    stmt.execute(&())?;
+    Ok(())
+}
+
+fn cancel_from_other_thread() -> HdbResult<()> {
+    info!("a running statement can be cancelled from another thread");
+    let mut connection = test_utils::get_authenticated_connection()?;
+    let cancel_handle = connection.cancel_handle()?;
+    let canceller = std::thread::spawn(move || {
+        std::thread::sleep(Duration::from_secs(1));
+        cancel_handle.cancel()
+    });
+
+    let start = Instant::now();
+    match connection.exec("DO BEGIN CALL SQLSCRIPT_SYNC:SLEEP_SECONDS(10); END") {
+        Err(HdbError::Cancelled { .. }) => {}
+        Err(e) => panic!("expected HdbError::Cancelled, got {:?}", e),
+        Ok(_) => panic!("statement was not cancelled"),
+    }
+    assert!(Instant::now().duration_since(start) < Duration::from_secs(10));
+    canceller.join().unwrap()?;
+
+    // the connection is still usable
+    connection.query("select 1 from dummy")?;
+
+    // without a running statement, a cancellation has no effect
+    connection.cancel_handle()?.cancel()?;
+    connection.query("select 1 from dummy")?;
+    Ok(())
+}
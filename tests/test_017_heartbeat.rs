@@ -0,0 +1,68 @@
+#[macro_use]
+extern crate serde;
+
+mod test_utils;
+
+use hdbconnect::{Connection, ConnectionManager, HdbResult};
+use log::*;
+use std::time::{Duration, Instant};
+
+#[test] // cargo test --test test_017_heartbeat -- --nocapture
+pub fn test_017_heartbeat() -> HdbResult<()> {
+    let _log_handle = test_utils::init_logger();
+    let start = Instant::now();
+
+    let mut cp_builder = test_utils::get_std_cp_builder()?;
+    cp_builder
+        .tcp_keepalive(Duration::from_secs(30))
+        .tcp_keepalive_interval(Duration::from_secs(5))
+        .tcp_keepalive_count(3)
+        .heartbeat_interval(Duration::from_secs(1));
+    let mut connection = Connection::new(cp_builder.clone())?;
+    info!("TESTING WITH {}", connection.connect_string()?);
+
+    idle_connection_is_pinged(&mut connection)?;
+    open_transaction_is_not_pinged(&mut connection)?;
+    pooled_connection_is_pinged(cp_builder)?;
+
+    test_utils::closing_info(connection, start)
+}
+
+fn idle_connection_is_pinged(connection: &mut Connection) -> HdbResult<()> {
+    info!("an idle connection is pinged and stays usable");
+    connection.query("select 1 from dummy")?;
+    let call_count = connection.get_call_count()?;
+    std::thread::sleep(Duration::from_millis(3500));
+    assert!(connection.get_call_count()? > call_count);
+    connection.query("select 1 from dummy")?;
+    Ok(())
+}
+
+fn open_transaction_is_not_pinged(connection: &mut Connection) -> HdbResult<()> {
+    info!("a connection with uncommitted changes is not pinged");
+    connection.multiple_statements_ignore_err(vec!["drop table TEST_HEARTBEAT"]);
+    connection.multiple_statements(vec!["create table TEST_HEARTBEAT (f1 INT)"])?;
+    connection.set_auto_commit(false)?;
+    connection.dml("insert into TEST_HEARTBEAT values(1)")?;
+    let call_count = connection.get_call_count()?;
+    std::thread::sleep(Duration::from_millis(3500));
+    assert_eq!(call_count, connection.get_call_count()?);
+    connection.rollback()?;
+    connection.set_auto_commit(true)?;
+    Ok(())
+}
+
+fn pooled_connection_is_pinged(cp_builder: hdbconnect::ConnectParamsBuilder) -> HdbResult<()> {
+    info!("idle connections in an r2d2 pool are pinged");
+    let manager = ConnectionManager::new(cp_builder)?;
+    // no validation on checkout, so that only the heartbeat increases the call count
+    let pool = r2d2::Pool::builder()
+        .max_size(1)
+        .test_on_check_out(false)
+        .build(manager)
+        .unwrap();
+    let call_count = pool.get().unwrap().get_call_count()?;
+    std::thread::sleep(Duration::from_millis(3500));
+    assert!(pool.get().unwrap().get_call_count()? > call_count);
+    Ok(())
+}
@@ -0,0 +1,80 @@
+#[macro_use]
+extern crate serde;
+
+mod test_utils;
+
+use hdbconnect::{ConnectParams, Connection, HdbError, HdbResult, Transport};
+use log::*;
+use std::net::TcpStream;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Instant;
+
+#[test] // cargo test --test test_018_transport -- --nocapture
+pub fn test_018_transport() -> HdbResult<()> {
+    let _log_handle = test_utils::init_logger();
+    let start = Instant::now();
+    transport_is_used_for_connect()?;
+    query_over_custom_transport()?;
+    info!("Elapsed time: {:?}", Instant::now().duration_since(start));
+    Ok(())
+}
+
+// A transport that behaves like a server that closes the connection immediately.
+struct ClosedTransport;
+impl std::io::Read for ClosedTransport {
+    fn read(&mut self, _buf: &mut [u8]) -> std::io::Result<usize> {
+        Ok(0)
+    }
+}
+impl std::io::Write for ClosedTransport {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        Ok(buf.len())
+    }
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+fn transport_is_used_for_connect() -> HdbResult<()> {
+    info!("the driver opens its connection with the transport factory");
+    let counter = Arc::new(AtomicUsize::new(0));
+    let factory_counter = Arc::clone(&counter);
+    let params = ConnectParams::builder()
+        .hostname("nowhere")
+        .port(2222)
+        .dbuser("MEIER")
+        .password("schlau")
+        .transport_factory(move |params: &ConnectParams| {
+            assert_eq!(params.addr(), "nowhere:2222");
+            factory_counter.fetch_add(1, Ordering::SeqCst);
+            Ok(Box::new(ClosedTransport) as Box<dyn Transport>)
+        })
+        .build()?;
+    assert!(params.has_transport_factory());
+
+    match Connection::new(params) {
+        Err(HdbError::Tcp { .. }) => {}
+        Err(e) => panic!("expected HdbError::Tcp, got {:?}", e),
+        Ok(_) => panic!("connect over a closed transport succeeded"),
+    }
+    assert_eq!(counter.load(Ordering::SeqCst), 1);
+    Ok(())
+}
+
+fn query_over_custom_transport() -> HdbResult<()> {
+    info!("run a query over a tcp connection that was opened by the application");
+    let mut cp_builder = test_utils::get_std_cp_builder()?;
+    cp_builder.transport_factory(|params: &ConnectParams| {
+        let tcpstream = TcpStream::connect(params.addr())?;
+        tcpstream.set_nodelay(true)?;
+        Ok(Box::new(tcpstream) as Box<dyn Transport>)
+    });
+    let mut connection = Connection::new(cp_builder)?;
+
+    let result: String = connection
+        .query("select 'hello over custom transport' from dummy")?
+        .try_into()?;
+    assert_eq!(result, "hello over custom transport");
+    Ok(())
+}
@@ -24,6 +24,7 @@ pub fn test_030_prepare() -> HdbResult<()> {
     prepare_select_with_pars(&mut log_handle, &mut connection)?;
     prepare_select_without_pars(&mut log_handle, &mut connection)?;
     prepare_and_execute_with_fetch(&mut log_handle, &mut connection)?;
+    statement_cache(&mut log_handle, &mut connection)?;
 
     test_utils::closing_info(connection, start)
 }
@@ -262,3 +263,57 @@ fn prepare_and_execute_with_fetch(
     }
     Ok(())
 }
+
+fn statement_cache(
+    _log_handle: &mut ReconfigurationHandle,
+    connection: &mut Connection,
+) -> HdbResult<()> {
+    info!("statements with the same SQL text are taken from the statement cache");
+    connection.set_statement_cache_size(2)?;
+    let stmt1 = "select count(*) from dummy where 1 = ?";
+    let stmt2 = "select count(*) from dummy where 2 = ?";
+    let stmt3 = "select count(*) from dummy where 3 = ?";
+
+    let count: u32 = connection
+        .prepare(stmt1)?
+        .execute(&1)?
+        .into_resultset()?
+        .try_into()?;
+    assert_eq!(count, 1);
+
+    debug!("a cache hit needs no roundtrip");
+    let call_count = connection.get_call_count()?;
+    let mut prepared_statement = connection.prepare(stmt1)?;
+    assert_eq!(connection.get_call_count()?, call_count);
+    let count: u32 = prepared_statement
+        .execute(&2)?
+        .into_resultset()?
+        .try_into()?;
+    assert_eq!(count, 0);
+
+    debug!("the least recently used statement is evicted");
+    connection.prepare(stmt2)?;
+    connection.prepare(stmt1)?;
+    connection.prepare(stmt3)?;
+    let call_count = connection.get_call_count()?;
+    connection.prepare(stmt1)?;
+    assert_eq!(connection.get_call_count()?, call_count);
+    connection.prepare(stmt2)?;
+    assert!(connection.get_call_count()? > call_count);
+
+    debug!("statements are cached per current schema");
+    let schema: String = connection
+        .query("select current_schema from dummy")?
+        .try_into()?;
+    connection.exec("set schema SYS")?;
+    let call_count = connection.get_call_count()?;
+    connection.prepare(stmt2)?;
+    assert!(connection.get_call_count()? > call_count);
+    let call_count = connection.get_call_count()?;
+    connection.prepare(stmt2)?;
+    assert_eq!(connection.get_call_count()?, call_count);
+    connection.exec(&format!("set schema \"{}\"", schema))?;
+
+    connection.set_statement_cache_size(0)?;
+    Ok(())
+}
@@ -3,7 +3,7 @@ extern crate serde;
 
 mod test_utils;
 
-use hdbconnect::{ConnectionManager, HdbResult};
+use hdbconnect::{ConnectionManager, ConnectionValidation, HdbResult};
 use log::trace;
 use std::thread::{self, JoinHandle};
 
@@ -38,5 +38,24 @@ fn test_080_conn_pooling_with_r2d2() -> HdbResult<()> {
             .unwrap_or_else(|e| panic!("Joining worker thread failed: {:?}", e));
     }
 
+    validation_with_ping()?;
+    Ok(())
+}
+
+fn validation_with_ping() -> HdbResult<()> {
+    let mut manager = ConnectionManager::new(test_utils::get_std_cp_builder()?)?;
+    manager.set_validation(ConnectionValidation::Ping);
+    let pool = r2d2::Pool::builder()
+        .max_size(2)
+        .test_on_check_out(true)
+        .build(manager)
+        .unwrap();
+
+    for _ in 0..5 {
+        let mut conn = pool.get().unwrap();
+        conn.ping()?;
+        conn.query("select 1 from dummy")?;
+        assert!(!conn.is_broken()?);
+    }
     Ok(())
 }
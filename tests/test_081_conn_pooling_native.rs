@@ -0,0 +1,139 @@
+#[macro_use]
+extern crate serde;
+
+mod test_utils;
+
+use hdbconnect::{ConnectionPool, ConnectionValidation, HdbResult};
+use log::*;
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+#[test] // cargo test --test test_081_conn_pooling_native -- --nocapture
+pub fn test_081_conn_pooling_native() -> HdbResult<()> {
+    let _log_handle = test_utils::init_logger();
+    let start = Instant::now();
+    pool_with_many_threads()?;
+    session_state_is_reset_on_check_in()?;
+    statements_are_kept_across_check_outs()?;
+    exhausted_pool_times_out()?;
+    info!("Elapsed time: {:?}", Instant::now().duration_since(start));
+    Ok(())
+}
+
+fn pool_with_many_threads() -> HdbResult<()> {
+    info!("many threads share a few connections");
+    let pool = ConnectionPool::builder()
+        .min_idle(2)
+        .max_size(5)
+        .health_check(Some(ConnectionValidation::Ping))
+        .build(test_utils::get_std_cp_builder()?)?;
+    assert_eq!(pool.idle_connections()?, 2);
+
+    let worker_handles: Vec<JoinHandle<HdbResult<()>>> = (0..20)
+        .map(|_| {
+            let pool = pool.clone();
+            thread::spawn(move || {
+                let mut connection = pool.get()?;
+                let one: u8 = connection.query("select 1 from dummy")?.try_into()?;
+                assert_eq!(one, 1);
+                Ok(())
+            })
+        })
+        .collect();
+    for worker_handle in worker_handles {
+        worker_handle.join().unwrap()?;
+    }
+    assert!(pool.connections()? <= 5);
+    Ok(())
+}
+
+fn session_state_is_reset_on_check_in() -> HdbResult<()> {
+    info!("the session state is reset when a connection is returned to the pool");
+    let pool = ConnectionPool::builder()
+        .max_size(1)
+        .build(test_utils::get_std_cp_builder()?)?;
+    let schema: String = {
+        let mut connection = pool.get()?;
+        let schema: String = connection
+            .query("select current_schema from dummy")?
+            .try_into()?;
+        connection.multiple_statements_ignore_err(vec!["drop table TEST_POOL_RESET"]);
+        connection.exec("create table TEST_POOL_RESET (F1 INT)")?;
+        connection.set_session_variable("POOL_VAR", "set")?;
+        connection.set_query_timeout(std::time::Duration::from_secs(10))?;
+        connection.exec("set transaction isolation level serializable")?;
+        connection.set_auto_commit(false)?;
+        connection.set_application_user("POOL_USER")?;
+        connection.dml("insert into TEST_POOL_RESET (F1) values (1)")?;
+        connection.exec("set schema SYS")?;
+        schema
+    };
+
+    let mut connection = pool.get()?;
+    assert!(connection.is_auto_commit()?);
+    let count: u32 = connection
+        .query("select count(*) from TEST_POOL_RESET")?
+        .try_into()?;
+    assert_eq!(count, 0);
+    let application_user: Option<String> = connection
+        .query("select session_context('APPLICATIONUSER') from dummy")?
+        .try_into()?;
+    assert_ne!(application_user, Some("POOL_USER".to_string()));
+    let pool_var: Option<String> = connection
+        .query("select session_context('POOL_VAR') from dummy")?
+        .try_into()?;
+    assert_eq!(pool_var, None);
+    let current_schema: String = connection
+        .query("select current_schema from dummy")?
+        .try_into()?;
+    assert_eq!(current_schema, schema);
+    assert_eq!(connection.query_timeout()?, None);
+    let isolation_level: String = connection
+        .query(
+            "select isolation_level from m_transactions \
+             where connection_id = current_connection",
+        )?
+        .try_into()?;
+    assert_eq!(isolation_level, "READ COMMITTED");
+    Ok(())
+}
+
+fn statements_are_kept_across_check_outs() -> HdbResult<()> {
+    info!("prepared statements survive check-in and check-out");
+    let pool = ConnectionPool::builder()
+        .max_size(1)
+        .build(test_utils::get_std_cp_builder()?)?;
+    let stmt = "select count(*) from dummy where 1 = ?";
+    {
+        let connection = pool.get()?;
+        let count: u32 = connection
+            .prepare(stmt)?
+            .execute(&1)?
+            .into_resultset()?
+            .try_into()?;
+        assert_eq!(count, 1);
+    }
+
+    let connection = pool.get()?;
+    let call_count = connection.get_call_count()?;
+    let mut prepared_statement = connection.prepare(stmt)?;
+    // the statement is taken from the statement cache, without a roundtrip
+    assert_eq!(connection.get_call_count()?, call_count);
+    let count: u32 = prepared_statement
+        .execute(&1)?
+        .into_resultset()?
+        .try_into()?;
+    assert_eq!(count, 1);
+    Ok(())
+}
+
+fn exhausted_pool_times_out() -> HdbResult<()> {
+    info!("an exhausted pool lets the caller wait only for the connection timeout");
+    let pool = ConnectionPool::builder()
+        .max_size(1)
+        .connection_timeout(Duration::from_millis(200))
+        .build(test_utils::get_std_cp_builder()?)?;
+    let _connection = pool.get()?;
+    assert!(pool.get().is_err());
+    Ok(())
+}
@@ -0,0 +1,235 @@
+#![cfg(feature = "async")]
+
+#[macro_use]
+extern crate serde;
+
+mod test_utils;
+
+use futures_core::Stream;
+use hdbconnect::{AsyncConnection, AsyncResultSet, HdbError, HdbResult, HdbValue};
+use log::{debug, info};
+use std::io::Cursor;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+// cargo test --features async --test test_100_async -- --nocapture
+#[tokio::test(flavor = "multi_thread")]
+pub async fn test_100_async() -> HdbResult<()> {
+    let _log_handle = test_utils::init_logger();
+    let start = std::time::Instant::now();
+    let mut connection = AsyncConnection::new(test_utils::get_std_cp_builder()?).await?;
+    info!("TESTING WITH {}", connection.connect_string()?);
+
+    statements_and_fetching(&mut connection).await?;
+    prepared_statements(&mut connection).await?;
+    lobs(&mut connection).await?;
+    dropped_future(&mut connection).await?;
+    cancel_from_other_task(&mut connection).await?;
+
+    info!(
+        "{} calls to DB were executed; elapsed time: {:?}",
+        connection.get_call_count()?,
+        std::time::Instant::now().duration_since(start),
+    );
+    Ok(())
+}
+
+// The futures must be usable with multi-threaded executors.
+#[allow(dead_code)]
+fn futures_are_send(mut connection: AsyncConnection) {
+    fn assert_send<T: Send>(_t: T) {}
+    assert_send(AsyncConnection::new("hdbsql://u:p@h:1"));
+    assert_send(connection.query("select 1 from dummy"));
+    assert_send(connection.prepare("select 1 from dummy"));
+}
+
+async fn statements_and_fetching(connection: &mut AsyncConnection) -> HdbResult<()> {
+    info!("run statements and fetch a resultset in several roundtrips");
+    connection
+        .statement("drop table TEST_ASYNC")
+        .await
+        .map(|_| ())
+        .unwrap_or_else(|e| debug!("Error intentionally ignored: {:?}", e));
+    connection
+        .multiple_statements(vec![
+            "create table TEST_ASYNC (F1_S NVARCHAR(20), F2_I INT)",
+            "insert into TEST_ASYNC (F1_S, F2_I) values('a', 1)",
+            "insert into TEST_ASYNC (F1_S, F2_I) values('b', 2)",
+            "insert into TEST_ASYNC (F1_S, F2_I) values('c', 3)",
+            "insert into TEST_ASYNC (F1_S, F2_I) values('d', 4)",
+            "insert into TEST_ASYNC (F1_S, F2_I) values('e', 5)",
+        ])
+        .await?;
+    assert_eq!(
+        connection
+            .dml("update TEST_ASYNC set F2_I = F2_I + 10 where F2_I > 3")
+            .await?,
+        2
+    );
+
+    connection.set_fetch_size(2)?;
+
+    // iterate with next_row()
+    let mut resultset = connection
+        .query("select F2_I from TEST_ASYNC order by F2_I")
+        .await?;
+    let mut sum = 0;
+    while let Some(row) = resultset.next_row().await? {
+        let i: i32 = row.into_single_value()?.try_into()?;
+        sum += i;
+    }
+    assert_eq!(sum, 26);
+
+    // iterate with the Stream implementation
+    let mut resultset = connection
+        .query("select F2_I from TEST_ASYNC order by F2_I")
+        .await?;
+    let mut count = 0;
+    while let Some(row) = std::future::poll_fn(|cx| Pin::new(&mut resultset).poll_next(cx)).await {
+        let _row = row?;
+        count += 1;
+    }
+    assert_eq!(count, 5);
+
+    // deserialize
+    let resultset = connection
+        .query("select F1_S from TEST_ASYNC order by F1_S")
+        .await?;
+    let strings: Vec<String> = resultset.try_into().await?;
+    assert_eq!(strings, vec!["a", "b", "c", "d", "e"]);
+
+    connection.set_fetch_size(hdbconnect::DEFAULT_FETCH_SIZE)?;
+    Ok(())
+}
+
+async fn prepared_statements(connection: &mut AsyncConnection) -> HdbResult<()> {
+    info!("use prepared statements");
+    connection.set_auto_commit(false)?;
+    let mut insert_stmt = connection
+        .prepare("insert into TEST_ASYNC (F1_S, F2_I) values(?, ?)")
+        .await?;
+    insert_stmt.add_batch(&("f", 6_i32))?;
+    insert_stmt.add_batch(&("g", 7_i32))?;
+    insert_stmt.execute_batch().await?;
+    insert_stmt.execute(&("h", 8_i32)).await?;
+    connection.rollback().await?;
+
+    let mut select_stmt = connection
+        .prepare("select count(*) from TEST_ASYNC where F2_I > ?")
+        .await?;
+    let resultset: AsyncResultSet = select_stmt.execute(&5_i32).await?.into_resultset()?.into();
+    let count: u32 = resultset
+        .into_single_row()
+        .await?
+        .into_single_value()?
+        .try_into()?;
+    assert_eq!(count, 2);
+
+    insert_stmt.execute(&("i", 9_i32)).await?;
+    connection.commit().await?;
+    connection.set_auto_commit(true)?;
+    Ok(())
+}
+
+async fn lobs(connection: &mut AsyncConnection) -> HdbResult<()> {
+    info!("write and read LOBs asynchronously");
+    connection
+        .statement("drop table TEST_ASYNC_LOBS")
+        .await
+        .map(|_| ())
+        .unwrap_or_else(|e| debug!("Error intentionally ignored: {:?}", e));
+    connection
+        .exec("create table TEST_ASYNC_LOBS (F1_S NVARCHAR(20), F2_NCLOB NCLOB, F3_BLOB BLOB)")
+        .await?;
+
+    let text = "Hello asynchronous world! 😀 ".repeat(10_000);
+    let bytes = text.as_bytes().to_vec();
+
+    connection.set_auto_commit(false)?;
+    connection.set_lob_write_length(50_000)?;
+    let mut insert_stmt = connection
+        .prepare("insert into TEST_ASYNC_LOBS values(?, ?, ?)")
+        .await?;
+    insert_stmt
+        .execute_row(vec![
+            HdbValue::STR("streamed"),
+            HdbValue::LOBSTREAM(Some(Arc::new(Mutex::new(Cursor::new(text.clone()))))),
+            HdbValue::LOBSTREAM(Some(Arc::new(Mutex::new(Cursor::new(bytes.clone()))))),
+        ])
+        .await?;
+    connection.commit().await?;
+    connection.set_auto_commit(true)?;
+
+    connection.set_lob_read_length(20_000)?;
+    let mut resultset = connection
+        .query("select F2_NCLOB, F3_BLOB from TEST_ASYNC_LOBS")
+        .await?;
+    let mut row = resultset.next_row().await?.unwrap();
+    let nclob = row.next_value().unwrap().try_into_nclob()?;
+    let mut blob = row.next_value().unwrap().try_into_blob()?;
+
+    assert_eq!(nclob.into_string_async().await?, text);
+
+    let mut streamed = Vec::<u8>::new();
+    let mut buf = vec![0_u8; 8 * 1024];
+    loop {
+        let count = blob.read_async(&mut buf).await?;
+        if count == 0 {
+            break;
+        }
+        streamed.extend_from_slice(&buf[0..count]);
+    }
+    assert_eq!(streamed, bytes);
+    assert!(blob.max_buf_len() < 40_000);
+    Ok(())
+}
+
+async fn dropped_future(connection: &mut AsyncConnection) -> HdbResult<()> {
+    info!("a future that is dropped halfway does not confuse the next call");
+    let result = tokio::time::timeout(
+        Duration::from_millis(500),
+        connection.exec("DO BEGIN CALL SQLSCRIPT_SYNC:SLEEP_SECONDS(2); END"),
+    )
+    .await;
+    assert!(
+        result.is_err(),
+        "statement was not interrupted by the timeout"
+    );
+    assert!(connection.is_broken()?);
+
+    // the next call re-establishes the connection and gets its own reply
+    let mut resultset = connection.query("select 'own reply' from dummy").await?;
+    let value: String = resultset
+        .next_row()
+        .await?
+        .unwrap()
+        .into_single_value()?
+        .try_into()?;
+    assert_eq!(value, "own reply");
+    assert!(!connection.is_broken()?);
+    Ok(())
+}
+
+async fn cancel_from_other_task(connection: &mut AsyncConnection) -> HdbResult<()> {
+    info!("a running statement can be cancelled from another task");
+    let cancel_handle = connection.cancel_handle()?;
+    let canceller = tokio::spawn(async move {
+        tokio::time::sleep(Duration::from_secs(1)).await;
+        cancel_handle.cancel_async().await
+    });
+
+    match connection
+        .exec("DO BEGIN CALL SQLSCRIPT_SYNC:SLEEP_SECONDS(10); END")
+        .await
+    {
+        Err(HdbError::Cancelled { .. }) => {}
+        Err(e) => panic!("expected HdbError::Cancelled, got {:?}", e),
+        Ok(_) => panic!("statement was not cancelled"),
+    }
+    canceller.await.unwrap()?;
+
+    // the connection is still usable
+    connection.query("select 1 from dummy").await?;
+    Ok(())
+}